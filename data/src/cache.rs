@@ -0,0 +1,54 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Response body of `url`, kept on disk so the last download can stand in
+/// when offline or when the download fails.
+///
+/// Online, the body is streamed into the cache file and read back from there,
+/// so large feeds still aren't held in memory.
+pub(crate) fn fetch(url: &str) -> Result<Box<dyn Read>, anyhow::Error> {
+	let Some(path) = path(url) else {
+		return Ok(Box::new(crate::http_get(url)?.into_body().into_reader()));
+	};
+	
+	if !crate::is_offline() {
+		match download(url, &path) {
+			Ok(()) => return Ok(Box::new(std::io::BufReader::new(std::fs::File::open(&path)?))),
+			Err(err) if path.exists() => println!("[Cache] Download of {url} failed, using the cached copy: {err}"),
+			Err(err) => return Err(err),
+		}
+	}
+	
+	match std::fs::File::open(&path) {
+		Ok(file) => Ok(Box::new(std::io::BufReader::new(file))),
+		Err(err) if crate::is_offline() => Err(anyhow::anyhow!("offline mode and no cached copy of {url}: {err}")),
+		Err(err) => Err(err.into()),
+	}
+}
+
+/// Download into a temporary file first, a failed download keeps the old copy
+fn download(url: &str, path: &Path) -> Result<(), anyhow::Error> {
+	if let Some(dir) = path.parent() {
+		std::fs::create_dir_all(dir)?;
+	}
+	
+	let tmp = path.with_extension("part");
+	let mut body = crate::http_get(url)?.into_body().into_reader();
+	std::io::copy(&mut body, &mut std::fs::File::create(&tmp)?)?;
+	std::fs::rename(&tmp, path)?;
+	Ok(())
+}
+
+/// Cache file of `url`: its host and last path segment. PublicExport manifest
+/// names carry a content hash after a `!`, which is left out so a new export
+/// replaces the old one and stays findable without the index.
+fn path(url: &str) -> Option<PathBuf> {
+	let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+	let host = rest.split('/').next()?;
+	let name = rest.rsplit('/').next()?.split(['!', '?']).next()?;
+	if host.is_empty() || name.is_empty() || name == host {
+		return None;
+	}
+	
+	Some(dirs::cache_dir()?.join("wfbuddy").join("http").join(host).join(name))
+}
@@ -1,32 +1,121 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const URL: &str = "https://warframe.com/droptables";
 
+/// How long the parsed droptable is reused before it is downloaded again
+const CACHE_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+/// Download attempts, waiting `RETRY_DELAY` doubled after each failure
+const ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
 pub struct Droptable {
 	items: HashSet<crate::Id>,
 }
 
+/// Relic names parsed from the droptables page, stored so startup doesn't
+/// have to download and regex the (large, slow) page every time
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Cache {
+	/// Unix time (seconds) the page was downloaded
+	fetched_at: u64,
+	relics: Vec<String>,
+}
+
 impl Droptable {
+	/// Relics currently in the droptables, from a fresh cache or downloaded.
+	///
+	/// If the download fails a stale cache is used rather than nothing.
 	pub fn downloaded(idman: &mut crate::IdManager) -> Result<Self, anyhow::Error> {
-		let html = ureq::get(URL)
-			.call()?
-			.body_mut()
-			.read_to_string()?;
+		let cached = load_cache();
+		let relics = match cached {
+			Some(cache) if age(&cache) < CACHE_TTL => cache.relics,
+			cached => match download_relic_names() {
+				Ok(relics) => {
+					save_cache(&relics);
+					relics
+				}
+				Err(err) => {
+					let Some(cache) = cached else {return Err(err)};
+					println!("[Droptable] Download failed, using cache from {}h ago: {err}", age(&cache).as_secs() / 3600);
+					cache.relics
+				}
+			},
+		};
 		
-		let regex = regex::Regex::new(r"<tr><td>(?:</td><td>)?(?<name>[^<]+)</td>")?;
-		let caps = regex.captures_iter(&html);
-		let items = caps
-			.filter_map(|cap| {
-				cap.name("name")
-					.filter(|name| name.as_str().ends_with("Relic"))
-					.and_then(|name| idman.get_id_from_en(name.as_str()))
-			})
+		let items = relics
+			.iter()
+			.filter_map(|name| idman.get_id_from_en(name))
 			.collect::<HashSet<_>>();
-
+		
 		Ok(Self { items })
 	}
 	
 	pub fn contains_id(&self, id: &crate::Id) -> bool {
 		self.items.contains(id)
 	}
-}
\ No newline at end of file
+}
+
+/// Download the page and parse the relic names out of it, retrying with backoff
+fn download_relic_names() -> Result<Vec<String>, anyhow::Error> {
+	let mut delay = RETRY_DELAY;
+	let mut attempt = 1;
+	let html = loop {
+		let res = crate::http_get(URL).and_then(|mut res| Ok(res.body_mut().read_to_string()?));
+		match res {
+			Ok(html) => break html,
+			// offline mode fails the same way every time
+			Err(err) if attempt >= ATTEMPTS || crate::is_offline() => return Err(err),
+			Err(err) => {
+				println!("[Droptable] Download attempt {attempt} failed, retrying in {}s: {err}", delay.as_secs());
+				std::thread::sleep(delay);
+				delay *= 2;
+				attempt += 1;
+			}
+		}
+	};
+	
+	let regex = regex::Regex::new(r"<tr><td>(?:</td><td>)?(?<name>[^<]+)</td>")?;
+	let relics = regex.captures_iter(&html)
+		.filter_map(|cap| cap.name("name"))
+		.map(|name| name.as_str())
+		.filter(|name| name.ends_with("Relic"))
+		.collect::<HashSet<_>>();
+	
+	let mut relics = relics.into_iter().map(str::to_string).collect::<Vec<_>>();
+	relics.sort();
+	Ok(relics)
+}
+
+fn cache_path() -> Option<PathBuf> {
+	Some(dirs::cache_dir()?.join("wfbuddy").join("droptable.json"))
+}
+
+fn load_cache() -> Option<Cache> {
+	let json = std::fs::read_to_string(cache_path()?).ok()?;
+	serde_json::from_str(&json).ok()
+}
+
+/// Best-effort, a missing cache only costs a download next time
+fn save_cache(relics: &[String]) {
+	let Some(path) = cache_path() else {return};
+	let cache = Cache {
+		fetched_at: now_secs(),
+		relics: relics.to_vec(),
+	};
+	let res = path.parent()
+		.map_or(Ok(()), std::fs::create_dir_all)
+		.and_then(|_| std::fs::write(&path, serde_json::to_string(&cache)?));
+	if let Err(err) = res {
+		println!("[Droptable] Failed to write cache {path:?}: {err}");
+	}
+}
+
+fn age(cache: &Cache) -> Duration {
+	Duration::from_secs(now_secs().saturating_sub(cache.fetched_at))
+}
+
+fn now_secs() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |v| v.as_secs())
+}
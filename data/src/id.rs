@@ -6,8 +6,8 @@ pub type Id = lasso::Spur;
 pub struct IdManager {
 	strings: lasso::Rodeo,
 	
-	map_en_gamename: HashMap<lasso::Spur, lasso::Spur>,
-	map_gamename_en: HashMap<lasso::Spur, lasso::Spur>,
+	map_locale_gamename: HashMap<(crate::Language, lasso::Spur), lasso::Spur>,
+	map_gamename_locale: HashMap<(crate::Language, lasso::Spur), lasso::Spur>,
 }
 
 impl Default for IdManager {
@@ -21,24 +21,22 @@ impl IdManager {
 		Self {
 			strings: lasso::Rodeo::new(),
 			
-			map_en_gamename: HashMap::new(),
-			map_gamename_en: HashMap::new(),
+			map_locale_gamename: HashMap::new(),
+			map_gamename_locale: HashMap::new(),
 		}
 	}
 	
 	pub fn add_locale<'a>(&mut self, locale_name: impl Into<super::Name<'a>>, gamename: impl Into<String>) {
 		let locale_name = locale_name.into();
-		match locale_name.lang {
-			crate::Language::English => self.add_locale_en(locale_name.text, gamename),
-		}
+		let gamename_key = self.strings.get_or_intern(convert_gamename(gamename));
+		let locale_name_key = self.strings.get_or_intern(convert_locale(locale_name.text));
+		self.map_locale_gamename.insert((locale_name.lang, locale_name_key), gamename_key);
+		self.map_gamename_locale.insert((locale_name.lang, gamename_key), locale_name_key);
 	}
 	
 	pub fn add_locale_en(&mut self, locale_name: impl Into<String>, gamename: impl Into<String>) {
-		let gamename = gamename.into();
-		let gamename_key = self.strings.get_or_intern(convert_gamename(gamename));
-		let locale_name_key = self.strings.get_or_intern(convert_en(locale_name));
-		self.map_en_gamename.insert(locale_name_key, gamename_key);
-		self.map_gamename_en.insert(gamename_key, locale_name_key);
+		let locale_name = locale_name.into();
+		self.add_locale((crate::Language::English, locale_name.as_str()), gamename);
 	}
 	
 	pub fn get_id_from_gamename(&self, name: &str) -> Option<Id> {
@@ -47,15 +45,13 @@ impl IdManager {
 	
 	pub fn get_id_from_locale<'a>(&self, locale_name: impl Into<super::Name<'a>>) -> Option<Id> {
 		let locale_name = locale_name.into();
-		match locale_name.lang {
-			crate::Language::English => self.get_id_from_en(locale_name.text),
-		}
+		self.map_locale_gamename
+			.get(&(locale_name.lang, self.strings.get(convert_locale(locale_name.text))?))
+			.copied()
 	}
 	
 	pub fn get_id_from_en(&self, name: &str) -> Option<Id> {
-		self.map_en_gamename
-			.get(&self.strings.get(convert_en(name))?)
-			.copied()
+		self.get_id_from_locale((crate::Language::English, name))
 	}
 	
 	pub fn get_gamename_from_id(&self, id: Id) -> Option<&str> {
@@ -68,34 +64,32 @@ impl IdManager {
 	}
 	
 	pub fn get_locale_from_id(&self, lang: crate::Language, id: Id) -> Option<&str> {
-		match lang {
-			crate::Language::English => self.get_en_from_id(id),
-		}
+		self.strings.try_resolve(self.map_gamename_locale.get(&(lang, id))?)
 	}
 	
 	pub fn get_en_from_id(&self, id: Id) -> Option<&str> {
-		self.strings.try_resolve(self.map_gamename_en.get(&id)?)
+		self.get_locale_from_id(crate::Language::English, id)
 	}
 	
 	pub fn get_closest_match<'a>(&self, locale_name: impl Into<super::Name<'a>>) -> &str {
 		let locale_name = locale_name.into();
-		match locale_name.lang {
-			crate::Language::English => self.get_closest_match_en(locale_name.text),
-		}
-	}
-	
-	pub fn get_closest_match_en<'a>(&'a self, name: &str) -> &'a str {
-		let check_name = convert_en(name);
-		if let Some(id) = self.get_id_from_en(&check_name) {
-			return self.get_en_from_id(id).unwrap();
+		let lang = locale_name.lang;
+		let check_name = convert_locale(locale_name.text);
+		if let Some(id) = self.get_id_from_locale((lang, check_name.as_str())) && let Some(name) = self.get_locale_from_id(lang, id) {
+			return name;
 		}
 		
 		let mut min_name = "";
 		let mut min = usize::MAX;
-		for (id, _) in self.map_en_gamename.iter() {
+		for (key_lang, id) in self.map_locale_gamename.keys() {
+			if *key_lang != lang {
+				continue;
+			}
+			
 			let item_name = self.strings.resolve(id);
-			let lev = levenshtein::levenshtein(name, item_name);
-			if lev < min {
+			let lev = levenshtein::levenshtein(locale_name.text, item_name);
+			// tie-break on the name so the result doesn't depend on HashMap order
+			if lev < min || (lev == min && item_name < min_name) {
 				min_name = item_name;
 				min = lev;
 			}
@@ -103,6 +97,10 @@ impl IdManager {
 		
 		min_name
 	}
+	
+	pub fn get_closest_match_en<'a>(&'a self, name: &str) -> &'a str {
+		self.get_closest_match((crate::Language::English, name))
+	}
 }
 
 fn convert_gamename(s: impl Into<String>) -> String {
@@ -112,7 +110,7 @@ fn convert_gamename(s: impl Into<String>) -> String {
 
 // since we return the locale, we wont adjust it for now
 // TODO: find solution
-fn convert_en(s: impl Into<String>) -> String {
+fn convert_locale(s: impl Into<String>) -> String {
 	s.into()
 	// let mut s = s.into();
 	// s.make_ascii_lowercase();
@@ -1,26 +1,88 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 mod structs;
 pub use structs::*;
 mod id;
 pub use id::*;
+mod cache;
 mod droptable;
 mod publicexport;
 mod market;
+pub use market::market_slug;
+
+const FORMA_BLUEPRINT: &str = "/Lotus/StoreItems/Types/Recipes/Components/FormaBlueprint";
+
+/// Approximate platinum value of `multiplier` forma blueprints.
+///
+/// Forma blueprints can't be traded, so there is no market price. The closest
+/// thing is the market's 3 forma bundle at 35 platinum, which puts a single
+/// blueprint at roughly 11.6 platinum (ignoring the build time and credits).
+pub fn forma_plat_value(multiplier: f32) -> f32 {
+	(350.0f32 / 3.0).floor() * 0.1 * multiplier
+}
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Never touch the network: every fetch (PublicExport, droptables, warframe.market)
+/// uses the copy cached by the last online run instead of attempting a request
+/// and timing out, and fails right away if there is none.
+pub fn set_offline(offline: bool) {
+	OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+	OFFLINE.load(Ordering::Relaxed)
+}
+
+/// GET `url`, the single place the data layer goes to the network
+pub(crate) fn http_get(url: &str) -> Result<ureq::http::Response<ureq::Body>, anyhow::Error> {
+	if is_offline() {
+		anyhow::bail!("offline mode, not fetching {url}");
+	}
+	Ok(ureq::get(url).call()?)
+}
 
 // TODO: maybe function to get platinum value, which calls api if its old or only
 // has value from ducanator, and updates it
 
 pub struct Data {
+	/// Client language the locale names were populated for
+	pub lang: Language,
 	pub id_manager: IdManager,
 	
+	/// warframe.market item id and url slug
+	pub market_items: HashMap<Id, MarketItem>,
 	pub platinum_values: HashMap<Id, f32>,
 	pub ducat_values: HashMap<Id, u32>,
 	pub relic_items: HashSet<Id>,
+	/// Rewards per relic id
+	pub relic_rewards: HashMap<Id, Vec<RelicDrop>>,
 	pub vaulted_items: HashSet<Id>,
+	/// Category of warframes, weapons and companions, including their blueprints and components
+	pub item_categories: HashMap<Id, ItemCategory>,
+	/// Relic ids currently in the drop tables, empty if those couldn't be fetched
+	active_relics: HashSet<Id>,
 }
 
 impl Data {
+	/// Data with only the given names and no market, relic or droptable data,
+	/// e.g. to test name lookups without a download
+	pub fn with_ids(lang: Language, id_manager: IdManager) -> Self {
+		Self {
+			lang,
+			id_manager,
+			market_items: HashMap::new(),
+			platinum_values: HashMap::new(),
+			ducat_values: HashMap::new(),
+			relic_items: HashSet::new(),
+			relic_rewards: HashMap::new(),
+			vaulted_items: HashSet::new(),
+			item_categories: HashMap::new(),
+			active_relics: HashSet::new(),
+		}
+	}
+	
 	pub fn populated(lang: Language) -> Result<Self, anyhow::Error> {
 		let mut idman = id::IdManager::new();
 		
@@ -32,19 +94,24 @@ impl Data {
 			idman.add_locale((lang, &v.name), &v.unique_name);
 		}
 		
+		let mut item_categories = HashMap::new();
+		
 		let warframes = get::<publicexport::warframes::Warframes>(&publicexport.warframes_url)?;
 		for v in &warframes.warframes {
 			idman.add_locale((lang, &v.name), &v.unique_name);
+			item_categories.insert(v.unique_name.clone(), ItemCategory::from_product_category(&v.product_category));
 		}
 		
 		let weapons = get::<publicexport::weapons::Weapons>(&publicexport.weapons_url)?;
 		for v in &weapons.weapons {
 			idman.add_locale((lang, &v.name), &v.unique_name);
+			item_categories.insert(v.unique_name.clone(), ItemCategory::from_product_category(&v.product_category));
 		}
 		
 		let sentinels = get::<publicexport::sentinels::Sentinels>(&publicexport.sentinels_url)?;
 		for v in &sentinels.sentinels {
 			idman.add_locale((lang, &v.name), &v.unique_name);
+			item_categories.insert(v.unique_name.clone(), ItemCategory::Companion);
 		}
 		
 		// blueprint locale
@@ -56,28 +123,70 @@ impl Data {
 			idman.add_locale((lang, &locale), &recipe.unique_name);
 		}
 		
+		// item categories, the main blueprint and its components inherit it from the result,
+		// component blueprints then inherit it from the component they build.
+		// Resources (ferrite, forma, ...) also show up as ingredients but aren't under /Recipes/
+		for recipe in &recipes.recipes {
+			let Some(&category) = item_categories.get(&recipe.result_type) else {continue};
+			item_categories.insert(recipe.unique_name.clone(), category);
+			for ingredient in recipe.ingredients.iter().filter(|v| v.item_type.contains("/Recipes/")) {
+				item_categories.entry(ingredient.item_type.clone()).or_insert(category);
+			}
+		}
+		
+		for recipe in &recipes.recipes {
+			let Some(&category) = item_categories.get(&recipe.result_type) else {continue};
+			item_categories.entry(recipe.unique_name.clone()).or_insert(category);
+		}
+		
+		let item_categories = item_categories
+			.into_iter()
+			.filter_map(|(gamename, category)| Some((idman.get_id_from_gamename(&gamename)?, category)))
+			.collect::<HashMap<_, _>>();
+		
 		//
 		let relicarcane = get::<publicexport::relicarcane::RelicArcane>(&publicexport.relic_arcane_url)?;
 		let mut relic_items = HashSet::new();
+		let mut relic_rewards = HashMap::new();
 		
 		for v in &relicarcane.items {
 			let publicexport::relicarcane::Item::Relic(relic) = v else {continue};
 			idman.add_locale((lang, &relic.name), &relic.unique_name);
+			let relic_id = idman.get_id_from_gamename(&relic.unique_name).unwrap();
 			for reward in &relic.relic_rewards {
 				let Some(id) = idman.get_id_from_gamename(&reward.reward_name) else {println!("[RelicItem] No id found for {}", reward.reward_name); continue};
 				relic_items.insert(id);
+				relic_rewards.entry(relic_id).or_insert_with(Vec::new).push(RelicDrop {
+					item: id,
+					rarity: reward.rarity,
+					count: reward.item_count.max(1) as u32,
+				});
 			}
 		}
 		
+		// the droptable and market slug fallback use english names, so those have to
+		// be known whatever the client language is
+		if lang != Language::English {
+			add_en_locales(&mut idman)?;
+		}
+		
 		//
-		let droptable = droptable::Droptable::downloaded(&mut idman)?;
+		// best-effort, without the droptable nothing is known to be vaulted or active
+		let droptable = match droptable::Droptable::downloaded(&mut idman) {
+			Ok(v) => Some(v),
+			Err(err) => {println!("[Droptable] Failed to download, vaulted/active relics unknown: {err}"); None}
+		};
 		let mut vaulted_items = HashSet::new();
+		let mut active_relics = HashSet::new();
 		let mut item_relics = HashMap::new();
 		
 		for v in relicarcane.items {
 			let publicexport::relicarcane::Item::Relic(relic) = v else {continue};
 			let relic_id = idman.get_id_from_gamename(&relic.unique_name).unwrap();
-			if !droptable.contains_id(&relic_id) {
+			let Some(droptable) = &droptable else {continue};
+			if droptable.contains_id(&relic_id) {
+				active_relics.insert(relic_id);
+			} else {
 				vaulted_items.insert(relic_id);
 			}
 			
@@ -97,26 +206,42 @@ impl Data {
 			vaulted_items.insert(item);
 		}
 		
-		// droptable is english localized names, so we gotta add enlgish relic locales to translate it
-		if lang != Language::English {
-			let publicexport = publicexport::PublicExport::new(Language::English)?;
-			let relicarcane = get::<publicexport::relicarcane::RelicArcane>(&publicexport.relic_arcane_url)?;
-			for v in relicarcane.items {
-				let publicexport::relicarcane::Item::Relic(relic) = v else {continue};
-				idman.add_locale_en(relic.name, relic.unique_name);
-			}
-		}
-		
 		//
-		let market_items = get::<market::items::Items>(market::items::URL)?;
-		let market_ducats = get::<market::ducats::Ducats>(market::ducats::URL)?;
 		let mut market_id_map = HashMap::new();
-		for v in &market_items.data {
-			let Some(id) = idman.get_id_from_gamename(&v.game_ref) else {println!("[WFMarket] No id found for {}", v.game_ref); continue};
+		let mut market_item_map = HashMap::new();
+		let mut market_total = 0;
+		let mut market_skipped = 0;
+		market::items::for_each(get_reader(market::items::URL)?, |v| {
+			market_total += 1;
+			let Some(id) = idman.get_id_from_gamename(&v.game_ref) else {
+				println!("[WFMarket] No id found for {}", v.game_ref);
+				market_skipped += 1;
+				return;
+			};
 			market_id_map.insert(v.id.clone(), id);
+			market_item_map.insert(id, MarketItem {
+				id: v.id,
+				slug: v.slug,
+				game_ref: v.game_ref,
+			});
+		})?;
+		let market_ducats = get::<market::ducats::Ducats>(market::ducats::URL)?;
+		
+		if market_skipped > 0 {
+			println!("[WFMarket] Skipped {market_skipped} of {market_total} items without a known id");
+		}
+		
+		// a stale item id in the ducats feed only costs that entry, not the whole load
+		let ducats_skipped = market_ducats.payload.previous_hour
+			.iter()
+			.filter(|v| !market_id_map.contains_key(&v.item))
+			.count();
+		if ducats_skipped > 0 {
+			println!("[WFMarket] Skipped {ducats_skipped} of {} ducat entries for unknown items", market_ducats.payload.previous_hour.len());
 		}
 		
 		let mut s = Self {
+			market_items: market_item_map,
 			platinum_values: market_ducats.payload.previous_hour
 				.iter()
 				.filter_map(|v| market_id_map.get(&v.item).map(|id| (*id, v.wa_price)))
@@ -128,11 +253,15 @@ impl Data {
 				.collect(),
 			
 			relic_items,
+			relic_rewards,
 			vaulted_items,
+			item_categories,
+			active_relics,
 			id_manager: idman,
+			lang,
 		};
 		
-		s.platinum_values.insert(s.id_manager.get_id_from_gamename("/Lotus/StoreItems/Types/Recipes/Components/FormaBlueprint").unwrap(), (350.0f32 / 3.0).floor() * 0.1);
+		s.platinum_values.insert(s.id_manager.get_id_from_gamename(FORMA_BLUEPRINT).unwrap(), forma_plat_value(1.0));
 		
 		// println!("{:#?}", s.vaulted_items);
 		// for id in &s.vaulted_items {
@@ -142,15 +271,230 @@ impl Data {
 		Ok(s)
 	}
 	
+	/// Relics that currently drop somewhere (not vaulted).
+	///
+	/// Empty if the droptable couldn't be fetched.
+	pub fn active_relics(&self) -> &HashSet<Id> {
+		&self.active_relics
+	}
+	
+	/// Whether the relic can currently be farmed
+	pub fn is_relic_active(&self, relic: Id) -> bool {
+		self.active_relics.contains(&relic)
+	}
+	
+	/// Whether the droptable was fetched, without it nothing is known to be active or vaulted
+	pub fn has_droptable(&self) -> bool {
+		!self.active_relics.is_empty()
+	}
+	
+	/// Id of a relic from its shown name, with or without the trailing "Relic" ("Meso N5")
+	pub fn relic_id(&self, name: &str) -> Option<Id> {
+		let name = name.trim();
+		let full = format!("{name} Relic");
+		self.id_manager.get_id_from_locale((self.lang, full.as_str()))
+			.or_else(|| self.id_manager.get_id_from_locale((self.lang, name)))
+			.filter(|id| self.relic_rewards.contains_key(id))
+	}
+	
+	/// Active relics that contain the item as a reward
+	pub fn active_relics_with(&self, item: Id) -> impl Iterator<Item = Id> + '_ {
+		self.active_relics
+			.iter()
+			.copied()
+			.filter(move |relic| self.relic_rewards.get(relic).is_some_and(|rewards| rewards.iter().any(|drop| drop.item == item)))
+	}
+	
+	/// Expected platinum value of opening the relic at the given refinement.
+	///
+	/// Rewards without a known price count as 0, unknown relics return 0.
+	pub fn relic_expected_value(&self, relic: Id, refinement: Refinement) -> f32 {
+		let Some(rewards) = self.relic_rewards.get(&relic) else {return 0.0};
+		rewards
+			.iter()
+			.map(|drop| {
+				let price = self.platinum_values.get(&drop.item).copied().unwrap_or(0.0);
+				refinement.chance(drop.rarity) * price * drop.count as f32
+			})
+			.sum()
+	}
+	
+	/// warframe.market url slug of an item, derived from its name if the market didn't provide one
+	pub fn market_slug(&self, id: Id) -> Option<String> {
+		if let Some(item) = self.market_items.get(&id) && !item.slug.is_empty() {
+			return Some(item.slug.clone());
+		}
+		
+		self.id_manager.get_en_from_id(id).map(market_slug)
+	}
+	
+	/// Localized name of the forma blueprint, as registered through [`Language::blueprint_name`]
+	pub fn forma_blueprint_name(&self) -> Option<&str> {
+		self.id_manager.get_locale_from_gamename(self.lang, FORMA_BLUEPRINT)
+	}
+	
+	/// Whether the (matched) name is a forma blueprint, including stacked ones like "2 X Forma Blueprint"
+	pub fn is_forma_blueprint(&self, name: &str) -> bool {
+		self.forma_blueprint_name().is_some_and(|forma| name.ends_with(forma))
+	}
+	
+	/// Number of forma blueprints in the name ("2 X Forma Blueprint" = 2), `None` if it isn't forma
+	pub fn forma_blueprint_count(&self, name: &str) -> Option<u32> {
+		if !self.is_forma_blueprint(name) {
+			return None;
+		}
+		
+		let count = name
+			.split_once(['X', 'x'])
+			.and_then(|(count, _)| count.trim().parse::<u32>().ok())
+			.unwrap_or(1);
+		Some(count.max(1))
+	}
+	
+	/// Attempts to find the closest item name among the given ids.
+	///
+	/// The ids come from PublicExport reward paths, which are the same game paths
+	/// warframe.market uses as `game_ref`, so the candidates always resolve to
+	/// market items regardless of how the display names are spelled.
+	pub fn find_item_name_among<'a>(&'a self, name: &str, candidates: impl IntoIterator<Item = Id>) -> Option<&'a str> {
+		candidates
+			.into_iter()
+			.filter_map(|id| self.id_manager.get_locale_from_id(self.lang, id))
+			// tie-break on the name, the candidates usually come in HashSet order
+			.min_by_key(|candidate| (levenshtein::levenshtein(name, candidate), *candidate))
+	}
+	
+	/// Attempts to find the closest reward name of a specific relic
+	pub fn find_relic_reward_name(&self, name: &str, relic: Id) -> Option<&str> {
+		let rewards = self.relic_rewards.get(&relic)?;
+		self.find_item_name_among(name, rewards.iter().map(|drop| drop.item))
+	}
+	
+	/// Attempts to find the closest item name that can drop from any relic,
+	/// falling back to the full item pool
+	pub fn find_any_relic_reward_name(&self, name: &str) -> &str {
+		self.find_item_name_among(name, self.relic_items.iter().copied())
+			.unwrap_or_else(|| self.find_item_name((self.lang, name)))
+	}
+	
+	/// Matches a dirty ocr string of a relic reward slot against the relic drops.
+	///
+	/// Unlike [`Data::find_any_relic_reward_name`] the returned name always resolves
+	/// to an id, and text too far from any name is reported as [`ItemMatch::Unreadable`]
+	/// instead of being forced onto the closest item.
+	pub fn match_relic_reward(&self, name: &str) -> ItemMatch<'_> {
+		if !name.chars().any(char::is_alphanumeric) {
+			return ItemMatch::Unreadable;
+		}
+		
+		let canonical = self.find_any_relic_reward_name(name);
+		let Some(id) = self.id_manager.get_id_from_locale((self.lang, canonical)) else {
+			return ItemMatch::Unreadable;
+		};
+		
+		if canonical == name {
+			return ItemMatch::Exact {id, name: canonical};
+		}
+		
+		// more than half the characters wrong is noise, not a misread name
+		let max_len = name.chars().count().max(canonical.chars().count());
+		if levenshtein::levenshtein(name, canonical) * 2 > max_len {
+			return ItemMatch::Unreadable;
+		}
+		
+		ItemMatch::Fuzzy {id, name: canonical}
+	}
+	
+	/// Searches the tradeable (market) items by name for a price lookup.
+	///
+	/// Case-insensitive substring matches come first (earlier match, then shorter
+	/// name first), followed by fuzzy matches ranked by edit distance. Names with
+	/// more than half the characters different are left out, like in
+	/// [`Data::match_relic_reward`]. At most `limit` results.
+	pub fn search(&self, query: &str, limit: usize) -> Vec<(&str, Id)> {
+		let query = query.trim().to_lowercase();
+		if query.is_empty() {
+			return Vec::new();
+		}
+		
+		let mut ranked = self.market_items
+			.keys()
+			.filter_map(|&id| {
+				let name = self.id_manager.get_locale_from_id(self.lang, id)?;
+				let lower = name.to_lowercase();
+				let rank = match lower.find(&query) {
+					Some(pos) => (0, pos, lower.len()),
+					None => {
+						let distance = levenshtein::levenshtein(&query, &lower);
+						let max_len = query.chars().count().max(lower.chars().count());
+						if distance * 2 > max_len {
+							return None;
+						}
+						(1, distance, lower.len())
+					}
+				};
+				Some((rank, name, id))
+			})
+			.collect::<Vec<_>>();
+		
+		// tie-break on the name, the market items come in HashMap order
+		ranked.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(b.1)));
+		ranked.truncate(limit);
+		ranked.into_iter().map(|(_, name, id)| (name, id)).collect()
+	}
+	
 	/// Attempts to find the closest item name from a dirty ocr string
 	pub fn find_item_name<'a, 'b>(&'a self, name: impl Into<Name<'b>>) -> &'a str {
 		self.id_manager.get_closest_match(name)
 	}
 }
 
+/// Register the english names of every item next to the client language ones
+fn add_en_locales(idman: &mut id::IdManager) -> Result<(), anyhow::Error> {
+	let publicexport = publicexport::PublicExport::new(Language::English)?;
+	
+	let resources = get::<publicexport::resources::Resources>(&publicexport.resources_url)?;
+	for v in resources.resources {
+		idman.add_locale_en(v.name, v.unique_name);
+	}
+	
+	let warframes = get::<publicexport::warframes::Warframes>(&publicexport.warframes_url)?;
+	for v in warframes.warframes {
+		idman.add_locale_en(v.name, v.unique_name);
+	}
+	
+	let weapons = get::<publicexport::weapons::Weapons>(&publicexport.weapons_url)?;
+	for v in weapons.weapons {
+		idman.add_locale_en(v.name, v.unique_name);
+	}
+	
+	let sentinels = get::<publicexport::sentinels::Sentinels>(&publicexport.sentinels_url)?;
+	for v in sentinels.sentinels {
+		idman.add_locale_en(v.name, v.unique_name);
+	}
+	
+	let recipes = get::<publicexport::recipes::Recipes>(&publicexport.recipes_url)?;
+	for recipe in recipes.recipes {
+		let Some(result_locale) = idman.get_locale_from_gamename(Language::English, &recipe.result_type) else {continue};
+		let locale = Language::English.blueprint_name(result_locale);
+		idman.add_locale_en(locale, recipe.unique_name);
+	}
+	
+	let relicarcane = get::<publicexport::relicarcane::RelicArcane>(&publicexport.relic_arcane_url)?;
+	for v in relicarcane.items {
+		let publicexport::relicarcane::Item::Relic(relic) = v else {continue};
+		idman.add_locale_en(relic.name, relic.unique_name);
+	}
+	
+	Ok(())
+}
+
+/// Fetch and parse `url`, from the cache when offline or the download fails
 fn get<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, anyhow::Error> {
-	Ok(ureq::get(url)
-		.call()?
-		.body_mut()
-		.read_json::<T>()?)
+	Ok(serde_json::from_reader(get_reader(url)?)?)
+}
+
+/// Response body of `url` as a reader, for feeds parsed while downloading
+fn get_reader(url: &str) -> Result<impl std::io::Read, anyhow::Error> {
+	cache::fetch(url)
 }
\ No newline at end of file
@@ -1,14 +1,80 @@
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
 pub const URL: &str = "https://api.warframe.market/v2/items";
 
-#[derive(serde::Deserialize)]
-pub struct Items {
-	pub data: Vec<Item>,
+/// Parse the items response (`{"data": [Item, ...], ...}`) from `reader`,
+/// handing each item to `f` as soon as it is parsed.
+///
+/// The feed is large, streaming it avoids holding the body and the whole
+/// `Vec<Item>` in memory next to the maps built from it.
+pub fn for_each(reader: impl std::io::Read, f: impl FnMut(Item)) -> Result<(), serde_json::Error> {
+	let mut de = serde_json::Deserializer::from_reader(std::io::BufReader::new(reader));
+	ItemsSeed(f).deserialize(&mut de)?;
+	de.end()
+}
+
+/// The response object, only `data` is looked at
+struct ItemsSeed<F>(F);
+
+impl<'de, F: FnMut(Item)> DeserializeSeed<'de> for ItemsSeed<F> {
+	type Value = ();
+	
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+		deserializer.deserialize_map(self)
+	}
+}
+
+impl<'de, F: FnMut(Item)> Visitor<'de> for ItemsSeed<F> {
+	type Value = ();
+	
+	fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str("an items response object")
+	}
+	
+	fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+		while let Some(key) = map.next_key::<String>()? {
+			if key == "data" {
+				map.next_value_seed(ItemSeq(&mut self.0))?;
+			} else {
+				map.next_value::<IgnoredAny>()?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// The `data` array
+struct ItemSeq<'a, F>(&'a mut F);
+
+impl<'de, F: FnMut(Item)> DeserializeSeed<'de> for ItemSeq<'_, F> {
+	type Value = ();
+	
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+		deserializer.deserialize_seq(self)
+	}
+}
+
+impl<'de, F: FnMut(Item)> Visitor<'de> for ItemSeq<'_, F> {
+	type Value = ();
+	
+	fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str("an array of items")
+	}
+	
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+		while let Some(item) = seq.next_element::<Item>()? {
+			(self.0)(item);
+		}
+		Ok(())
+	}
 }
 
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Item {
 	pub id: String,
+	#[serde(default)]
+	pub slug: String,
 	pub game_ref: String,
 	// pub ducats: Option<u32>,
 	// pub i18n: Locale,
@@ -1,2 +1,21 @@
 pub mod items;
-pub mod ducats;
\ No newline at end of file
+pub mod ducats;
+
+/// Derive the warframe.market url slug from an item name
+/// ("Ack & Brunt Prime Blueprint" -> "ack_and_brunt_prime_blueprint")
+pub fn market_slug(name: &str) -> String {
+	let name = name.trim().to_lowercase().replace('&', "and");
+	let mut slug = String::with_capacity(name.len());
+	for c in name.chars() {
+		if c.is_alphanumeric() {
+			slug.push(c);
+		} else if c.is_whitespace() || c == '-' {
+			if !slug.is_empty() && !slug.ends_with('_') {
+				slug.push('_');
+			}
+		}
+		// other punctuation (apostrophes, dots) is dropped
+	}
+	
+	slug.trim_end_matches('_').to_string()
+}
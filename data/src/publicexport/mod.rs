@@ -5,8 +5,11 @@ pub mod warframes;
 pub mod weapons;
 pub mod sentinels;
 
+use std::io::Read;
+
 const URL_MANIFEST: &str = "http://content.warframe.com/PublicExport/Manifest/";
 const URL_EN: &str = "https://origin.warframe.com/PublicExport/index_en.txt.lzma";
+const URL_DE: &str = "https://origin.warframe.com/PublicExport/index_de.txt.lzma";
 
 // TODO: maybe lazyload the subpages
 pub struct PublicExport {
@@ -20,10 +23,8 @@ pub struct PublicExport {
 
 impl PublicExport {
 	fn new_url(url: &str) -> Result<Self, anyhow::Error> {
-		let data = ureq::get(url)
-			.call()?
-			.body_mut()
-			.read_to_vec()?;
+		let mut data = Vec::new();
+		crate::cache::fetch(url)?.read_to_end(&mut data)?;
 		
 		let mut urls = Vec::new();
 		lzma_rs::lzma_decompress(&mut std::io::Cursor::new(data), &mut urls)?;
@@ -49,6 +50,7 @@ impl PublicExport {
 	pub fn new(lang: crate::Language) -> Result<Self, anyhow::Error> {
 		match lang {
 			crate::Language::English => Self::new_url(URL_EN),
+			crate::Language::German => Self::new_url(URL_DE),
 		}
 	}
 }
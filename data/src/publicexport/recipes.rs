@@ -9,15 +9,16 @@ pub struct Recipes {
 pub struct Recipe {
 	pub unique_name: String,
 	pub result_type: String,
-	// pub ingredients: Vec<Ingredient>,
+	#[serde(default)]
+	pub ingredients: Vec<Ingredient>,
 }
 
-// #[derive(serde::Deserialize)]
-// #[serde(rename_all = "camelCase")]
-// pub struct Ingredient {
-// 	pub item_type: String,
-// 	pub item_count: i32,
-// }
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Ingredient {
+	pub item_type: String,
+	pub item_count: i32,
+}
 
 // {
 // 	"uniqueName": "/Lotus/Types/Recipes/WarframeRecipes/ZephyrPrimeChassisBlueprint",
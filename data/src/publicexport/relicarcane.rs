@@ -31,6 +31,7 @@ pub struct Relic {
 #[serde(rename_all = "camelCase")]
 pub struct RelicReward {
 	pub reward_name: String,
+	pub rarity: crate::Rarity,
 	pub item_count: i32,
 }
 
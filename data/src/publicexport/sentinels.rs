@@ -9,6 +9,8 @@ pub struct Sentinels {
 pub struct Sentinel {
 	pub unique_name: String,
 	pub name: String,
+	#[serde(default)]
+	pub product_category: String,
 }
 
 // {
@@ -9,6 +9,8 @@ pub struct Warframes {
 pub struct Warframe {
 	pub unique_name: String,
 	pub name: String,
+	#[serde(default)]
+	pub product_category: String,
 }
 
 // {
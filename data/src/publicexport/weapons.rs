@@ -9,6 +9,8 @@ pub struct Weapons {
 pub struct Weapon {
 	pub unique_name: String,
 	pub name: String,
+	#[serde(default)]
+	pub product_category: String,
 }
 
 // {
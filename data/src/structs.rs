@@ -1,18 +1,41 @@
-#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
 pub enum Language {
+	#[default]
 	English,
+	German,
 }
 
 impl Language {
+	pub const ALL: [Language; 2] = [Language::English, Language::German];
+	
 	pub fn ocr_code(&self) -> &'static str {
 		match self {
 			Self::English => "latin",
+			Self::German => "latin",
 		}
 	}
 	
 	pub fn blueprint_name(&self, name: &str) -> String {
 		match self {
 			Language::English => format!("{name} Blueprint"),
+			Language::German => format!("{name} Blaupause"),
+		}
+	}
+	
+	/// Lowercase header text shown above the party avatars on the relic reward screen
+	pub fn party_header_match_string(&self) -> &'static str {
+		match self {
+			Language::English => "void fissure/rewards",
+			Language::German => "void-riss/belohnungen",
+		}
+	}
+}
+
+impl std::fmt::Display for Language {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Language::English => write!(f, "English"),
+			Language::German => write!(f, "Deutsch"),
 		}
 	}
 }
@@ -49,8 +72,150 @@ impl<'a> From<(crate::Language, &'a str)> for Name<'a> {
 	}
 }
 
+/// Identifiers of an item on warframe.market
+#[derive(Debug, Clone)]
+pub struct MarketItem {
+	pub id: String,
+	pub slug: String,
+	/// Game path (`/Lotus/StoreItems/...`), matches the PublicExport unique names
+	pub game_ref: String,
+}
+
 pub enum Vaulted {
 	Unvaulted,
 	Vaulted,
 	Resurgence,
-}
\ No newline at end of file
+}
+
+/// Drop rarity of a relic reward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Rarity {
+	Common,
+	Uncommon,
+	Rare,
+}
+
+/// Relic refinement level, which shifts the drop chances towards rarer rewards
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Refinement {
+	#[default]
+	Intact,
+	Exceptional,
+	Flawless,
+	Radiant,
+}
+
+impl Refinement {
+	pub const ALL: [Refinement; 4] = [Refinement::Intact, Refinement::Exceptional, Refinement::Flawless, Refinement::Radiant];
+	
+	/// Drop chance of a single reward of the given rarity (a relic has 3 common, 2 uncommon and 1 rare reward)
+	pub fn chance(&self, rarity: Rarity) -> f32 {
+		match (self, rarity) {
+			(Refinement::Intact, Rarity::Common) => 0.2533,
+			(Refinement::Intact, Rarity::Uncommon) => 0.11,
+			(Refinement::Intact, Rarity::Rare) => 0.02,
+			(Refinement::Exceptional, Rarity::Common) => 0.2333,
+			(Refinement::Exceptional, Rarity::Uncommon) => 0.13,
+			(Refinement::Exceptional, Rarity::Rare) => 0.04,
+			(Refinement::Flawless, Rarity::Common) => 0.20,
+			(Refinement::Flawless, Rarity::Uncommon) => 0.17,
+			(Refinement::Flawless, Rarity::Rare) => 0.06,
+			(Refinement::Radiant, Rarity::Common) => 0.1667,
+			(Refinement::Radiant, Rarity::Uncommon) => 0.20,
+			(Refinement::Radiant, Rarity::Rare) => 0.10,
+		}
+	}
+}
+
+impl std::fmt::Display for Refinement {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Refinement::Intact => write!(f, "Intact"),
+			Refinement::Exceptional => write!(f, "Exceptional"),
+			Refinement::Flawless => write!(f, "Flawless"),
+			Refinement::Radiant => write!(f, "Radiant"),
+		}
+	}
+}
+
+/// Broad kind of item, derived from the PublicExport product category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemCategory {
+	Warframe,
+	Primary,
+	Secondary,
+	Melee,
+	Companion,
+	Archwing,
+	Other,
+}
+
+impl ItemCategory {
+	/// Maps a PublicExport `productCategory` ("LongGuns", "Suits", ...)
+	pub fn from_product_category(category: &str) -> Self {
+		match category {
+			"Suits" | "MechSuits" => ItemCategory::Warframe,
+			"LongGuns" => ItemCategory::Primary,
+			"Pistols" => ItemCategory::Secondary,
+			"Melee" => ItemCategory::Melee,
+			"Sentinels" | "SentinelWeapons" | "SpecialItems" | "KubrowPets" => ItemCategory::Companion,
+			"SpaceSuits" | "SpaceGuns" | "SpaceMelee" => ItemCategory::Archwing,
+			_ => ItemCategory::Other,
+		}
+	}
+}
+
+impl std::fmt::Display for ItemCategory {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ItemCategory::Warframe => write!(f, "Warframe"),
+			ItemCategory::Primary => write!(f, "Primary"),
+			ItemCategory::Secondary => write!(f, "Secondary"),
+			ItemCategory::Melee => write!(f, "Melee"),
+			ItemCategory::Companion => write!(f, "Companion"),
+			ItemCategory::Archwing => write!(f, "Archwing"),
+			ItemCategory::Other => write!(f, "Other"),
+		}
+	}
+}
+
+/// A single reward of a relic
+#[derive(Debug, Clone, Copy)]
+pub struct RelicDrop {
+	pub item: crate::Id,
+	pub rarity: Rarity,
+	/// Stack size (e.g. 2 for "2 X Forma Blueprint")
+	pub count: u32,
+}
+
+/// Result of matching a dirty ocr string against the known item names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemMatch<'a> {
+	/// The ocr text was exactly a known name
+	Exact {id: crate::Id, name: &'a str},
+	/// Closest known name, the ocr text differed from it
+	Fuzzy {id: crate::Id, name: &'a str},
+	/// Nothing in the text resembles a known name (empty, punctuation, garbage)
+	Unreadable,
+}
+
+impl<'a> ItemMatch<'a> {
+	pub fn id(&self) -> Option<crate::Id> {
+		match self {
+			ItemMatch::Exact {id, ..} | ItemMatch::Fuzzy {id, ..} => Some(*id),
+			ItemMatch::Unreadable => None,
+		}
+	}
+	
+	pub fn name(&self) -> Option<&'a str> {
+		match self {
+			ItemMatch::Exact {name, ..} | ItemMatch::Fuzzy {name, ..} => Some(name),
+			ItemMatch::Unreadable => None,
+		}
+	}
+	
+	pub fn is_fuzzy(&self) -> bool {
+		matches!(self, ItemMatch::Fuzzy {..})
+	}
+}
@@ -0,0 +1,51 @@
+//! OCR pipeline benchmarks on the bundled reward screen sample, scaled to
+//! 1080p. Needs the `ocr/` models at the workspace root.
+//!
+//! `cargo bench -p ie`
+
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ie::screen::relicreward;
+use ie::{Ocr, OwnedImage, Theme};
+
+const SAMPLE: &[u8] = include_bytes!("../src/asset/selftest_reward.png");
+
+fn sample_1080p() -> OwnedImage {
+    OwnedImage::from_encoded(SAMPLE).expect("decode sample").resized_h(1080)
+}
+
+fn ocr() -> Ocr {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../ocr");
+    Ocr::try_new(
+        dir.join("detection.mnn"),
+        dir.join("latin_recognition.mnn"),
+        dir.join("latin_charset.txt"),
+        ie::DEFAULT_MIN_CONFIDENCE,
+    )
+    .expect("OCR models in ocr/ (see the model_downloader binary)")
+}
+
+fn pipeline(c: &mut Criterion) {
+    let img = sample_1080p();
+    let image = img.as_image();
+    let ocr = ocr();
+
+    c.bench_function("detect_reward_slots", |b| b.iter(|| relicreward::detect_reward_slots(image, 1.0)));
+
+    // The name line of the first slot, the crop size get_text sees per reward.
+    let slot = relicreward::detect_reward_slots(image, 1.0)[0];
+    let name = image.sub_image(slot.x, slot.y + slot.h * 3 / 4, slot.w, slot.h / 4);
+    c.bench_function("get_text", |b| b.iter(|| name.get_text(Theme::WHITE, &ocr)));
+
+    let mut group = c.benchmark_group("full");
+    // Every sample runs a few dozen OCR passes.
+    group.sample_size(10);
+    group.bench_function("get_rewards", |b| {
+        b.iter(|| relicreward::get_rewards(image, 1.0, Theme::WHITE, &ocr, &[]))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, pipeline);
+criterion_main!(benches);
@@ -0,0 +1,27 @@
+//! Opt-in logging of detection decisions.
+//!
+//! Set `WFBUDDY_DEBUG_DETECT=1` to print what the detectors found and why
+//! candidates were rejected, as `key=value` lines on stderr.
+
+use std::sync::LazyLock;
+
+static ENABLED: LazyLock<bool> = LazyLock::new(|| std::env::var("WFBUDDY_DEBUG_DETECT").as_deref() == Ok("1"));
+
+/// Whether `WFBUDDY_DEBUG_DETECT` is on (read once).
+#[inline]
+pub(crate) fn enabled() -> bool {
+    *ENABLED
+}
+
+/// Log a detection decision when `WFBUDDY_DEBUG_DETECT=1`.
+///
+/// The arguments are only evaluated when logging is enabled.
+macro_rules! detect_debug {
+    ($($arg:tt)*) => {
+        if $crate::debug::enabled() {
+            eprintln!("[detect] {}", format_args!($($arg)*));
+        }
+    };
+}
+
+pub(crate) use detect_debug;
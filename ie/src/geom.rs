@@ -0,0 +1,126 @@
+//! Geometry primitives shared by the detectors.
+
+/// Axis-aligned rectangle in image coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl Rect {
+    #[inline]
+    pub const fn new(x: u32, y: u32, w: u32, h: u32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    #[inline]
+    pub fn right(&self) -> u32 {
+        self.x + self.w
+    }
+
+    #[inline]
+    pub fn bottom(&self) -> u32 {
+        self.y + self.h
+    }
+
+    #[inline]
+    pub fn center_x(&self) -> u32 {
+        self.x + self.w / 2
+    }
+
+    #[inline]
+    pub fn center_y(&self) -> u32 {
+        self.y + self.h / 2
+    }
+
+    #[inline]
+    pub fn area(&self) -> u64 {
+        self.w as u64 * self.h as u64
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.w == 0 || self.h == 0
+    }
+
+    /// Whether the point lies inside (right/bottom edges are exclusive).
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Overlapping region, or `None` if the rects don't overlap.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x1 = self.x.max(other.x);
+        let y1 = self.y.max(other.y);
+        let x2 = self.right().min(other.right());
+        let y2 = self.bottom().min(other.bottom());
+
+        if x2 <= x1 || y2 <= y1 {
+            return None;
+        }
+
+        Some(Rect::new(x1, y1, x2 - x1, y2 - y1))
+    }
+
+    /// Smallest rect containing both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x1 = self.x.min(other.x);
+        let y1 = self.y.min(other.y);
+        let x2 = self.right().max(other.right());
+        let y2 = self.bottom().max(other.bottom());
+        Rect::new(x1, y1, x2 - x1, y2 - y1)
+    }
+
+    /// Intersection over union (0.0 when disjoint).
+    pub fn iou(&self, other: &Rect) -> f32 {
+        let Some(inter) = self.intersect(other) else {
+            return 0.0;
+        };
+
+        let inter = inter.area() as f32;
+        inter / (self.area() as f32 + other.area() as f32 - inter)
+    }
+}
+
+/// Rectangle expressed as fractions of an image's size.
+///
+/// Detectors describe their crops with these so the resolution math lives in
+/// one place ([`RelativeRect::to_pixels`]) instead of inline ratios.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct RelativeRect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl RelativeRect {
+    /// Width of the reference capture most ratios were measured on.
+    pub const REF_W: f32 = 1920.0;
+    /// Height of the reference capture most ratios were measured on.
+    pub const REF_H: f32 = 1080.0;
+
+    #[inline]
+    pub const fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    /// Build from pixel coordinates measured on a 1920×1080 capture.
+    pub const fn from_1080p(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self::new(x / Self::REF_W, y / Self::REF_H, w / Self::REF_W, h / Self::REF_H)
+    }
+
+    /// Resolve to pixels for the given image (width/height are at least 1px).
+    pub fn to_pixels(&self, image: crate::Image) -> Rect {
+        let iw = image.width() as f32;
+        let ih = image.height() as f32;
+        Rect::new(
+            (self.x * iw).round() as u32,
+            (self.y * ih).round() as u32,
+            (self.w * iw).round().max(1.0) as u32,
+            (self.h * ih).round().max(1.0) as u32,
+        )
+    }
+}
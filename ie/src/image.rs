@@ -7,6 +7,8 @@
 //! This keeps UI/CV pipelines fast while still allowing easy conversion to owned
 //! images when needed (OCR preprocessing, debug snapshots, etc.).
 
+use std::sync::LazyLock;
+
 use anyhow::{Context, Result};
 
 pub struct OwnedMask(pub Vec<u8>);
@@ -38,6 +40,27 @@ impl OwnedImage {
         }
     }
 
+    /// Decode a PNG (alpha is discarded).
+    pub fn from_png(bytes: &[u8]) -> Result<Self> {
+        Self::from_encoded(bytes)
+    }
+
+    /// Decode an encoded image, guessing the format (PNG, JPEG, ...) from its contents.
+    pub fn from_encoded(bytes: &[u8]) -> Result<Self> {
+        let img = image::load_from_memory(bytes).context("decode image")?.to_rgb8();
+        let (width, height) = img.dimensions();
+        let data = img
+            .pixels()
+            .map(|p| Color::new(p.0[0], p.0[1], p.0[2]))
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
     /// Load an RGBA PNG and return an `(OwnedImage, OwnedMask)` pair.
     ///
     /// The mask is a packed bitset (row-major) where each bit indicates whether
@@ -70,14 +93,31 @@ impl OwnedImage {
 
     /// Resize this image to the given height (preserving aspect ratio).
     ///
-    /// Uses `fast_image_resize` (SIMD-optimized) and keeps output in `Vec<Color>`.
+    /// Degenerate images (zero-sized, or data not matching the dimensions) are
+    /// left unchanged; use [`OwnedImage::try_resize_h`] to get the error instead.
     pub fn resize_h(&mut self, height: u32) {
+        let _ = self.try_resize_h(height);
+    }
+
+    /// Resize this image to the given height (preserving aspect ratio).
+    ///
+    /// Uses `fast_image_resize` (SIMD-optimized) and keeps output in `Vec<Color>`.
+    /// Fails without touching the image if it is degenerate.
+    pub fn try_resize_h(&mut self, height: u32) -> Result<()> {
+        if self.width == 0 || self.height == 0 || self.data.len() != (self.width * self.height) as usize {
+            anyhow::bail!(
+                "cannot resize degenerate image ({}x{}, {} pixels)",
+                self.width,
+                self.height,
+                self.data.len()
+            );
+        }
         if self.height == height {
-            return;
+            return Ok(());
         }
 
         let height = height.max(1);
-        let width = (self.width as u64 * height as u64 / self.height.max(1) as u64) as u32;
+        let width = ((self.width as u64 * height as u64 / self.height as u64) as u32).max(1);
 
         // SAFETY: `Color` is `#[repr(C)]` with 3 x `u8`, so it is layout-compatible
         // with `fast_image_resize::pixels::U8x3` (alignment 1).
@@ -89,7 +129,7 @@ impl OwnedImage {
         };
 
         let src = fast_image_resize::images::ImageRef::from_pixels(self.width, self.height, src_pixels)
-            .expect("fast_image_resize: ImageRef::from_pixels failed");
+            .context("fast_image_resize: ImageRef::from_pixels failed")?;
 
         let mut dst = fast_image_resize::images::Image::new(width, height, fast_image_resize::PixelType::U8x3);
 
@@ -100,7 +140,7 @@ impl OwnedImage {
 
         resizer
             .resize(&src, &mut dst, &Some(options))
-            .expect("fast_image_resize: resize failed");
+            .context("fast_image_resize: resize failed")?;
 
         let bytes: Vec<u8> = dst.into_vec();
         let mut data = Vec::with_capacity((width * height) as usize);
@@ -111,6 +151,7 @@ impl OwnedImage {
         self.width = width;
         self.height = height;
         self.data = data;
+        Ok(())
     }
 
     #[inline]
@@ -125,6 +166,80 @@ impl OwnedImage {
         }
     }
 
+    /// Draw the outline of `rect` (growing inwards by `thickness`), clipped to
+    /// the image. Meant for annotating debug snapshots with detected regions.
+    pub fn draw_rect(&mut self, rect: crate::Rect, color: Color, thickness: u32) {
+        let x2 = rect.right().min(self.width);
+        let y2 = rect.bottom().min(self.height);
+        let t = thickness.max(1);
+        for y in rect.y.min(y2)..y2 {
+            for x in rect.x.min(x2)..x2 {
+                let edge = x < rect.x + t || y < rect.y + t || x + t >= rect.right() || y + t >= rect.bottom();
+                if edge {
+                    self.data[(x + y * self.width) as usize] = color;
+                }
+            }
+        }
+    }
+
+    /// Blend `color` over `rect` with the given opacity (see [`Color::blend`]),
+    /// clipped to the image; e.g. to tint a detected slot in a debug snapshot.
+    pub fn fill_rect(&mut self, rect: crate::Rect, color: Color, alpha: f32) {
+        let x2 = rect.right().min(self.width);
+        let y2 = rect.bottom().min(self.height);
+        for y in rect.y.min(y2)..y2 {
+            for x in rect.x.min(x2)..x2 {
+                let px = &mut self.data[(x + y * self.width) as usize];
+                *px = px.blend(color, alpha);
+            }
+        }
+    }
+
+    /// Cheap check for black/empty frames (e.g. right after alt-tab).
+    ///
+    /// Samples a fixed 16×16 grid and reports blank if the image is zero-sized,
+    /// or if nearly all samples have every channel at or below `threshold` and
+    /// the luma of the dark samples is practically flat. Dark but real scenes
+    /// (the Void, night tilesets) still have some bright pixels or texture and
+    /// aren't blank.
+    pub fn is_blank(&self, threshold: u8) -> bool {
+        const GRID: u32 = 16;
+        /// Samples allowed above `threshold` (stray cursor or HUD pixels).
+        const MAX_BRIGHT: u32 = GRID * GRID / 64;
+        /// Luma variance a black frame with encoder noise stays below.
+        const MAX_VARIANCE: f32 = 4.0;
+
+        if self.width == 0 || self.height == 0 || self.data.is_empty() {
+            return true;
+        }
+
+        let mut bright = 0;
+        let mut sum = 0u32;
+        let mut sum_sq = 0u32;
+        for gy in 0..GRID {
+            let y = (gy * 2 + 1) * self.height / (GRID * 2);
+            for gx in 0..GRID {
+                let x = (gx * 2 + 1) * self.width / (GRID * 2);
+                let c = self.data[(x + y * self.width) as usize];
+                if c.r.max(c.g).max(c.b) > threshold {
+                    bright += 1;
+                    if bright > MAX_BRIGHT {
+                        return false;
+                    }
+                    // the tolerated bright samples would dominate the variance
+                    continue;
+                }
+                let l = c.luma() as u32;
+                sum += l;
+                sum_sq += l * l;
+            }
+        }
+
+        let n = (GRID * GRID - bright) as f32;
+        let mean = sum as f32 / n;
+        sum_sq as f32 / n - mean * mean <= MAX_VARIANCE
+    }
+
     /// Create a borrowed view of this entire image.
     pub fn as_image<'a>(&'a self) -> Image<'a> {
         Image {
@@ -137,6 +252,11 @@ impl OwnedImage {
         }
     }
 
+    /// Tightly packed RGBA bytes (`width * height * 4`, alpha 255), e.g. for GUI textures.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.as_image().to_rgba()
+    }
+
     /// Convert to a grayscale `GrayImage` (luma).
     pub fn to_gray_image(&self) -> image::GrayImage {
         use image::{GrayImage, Luma};
@@ -190,11 +310,22 @@ impl<'a> Image<'a> {
         self.y2 - self.y1
     }
 
+    /// Height of the full capture this view was cut from.
+    #[inline]
+    pub fn source_height(&self) -> u32 {
+        (self.data.len() / self.true_width.max(1) as usize) as u32
+    }
+
     #[inline(always)]
     fn pixel(&self, x: u32, y: u32) -> &Color {
         &self.data[(x + y * self.true_width) as usize]
     }
 
+    /// Pixels row by row, left to right.
+    pub fn pixels(&self) -> impl Iterator<Item = Color> + '_ {
+        (self.y1..self.y2).flat_map(move |y| (self.x1..self.x2).map(move |x| *self.pixel(x, y)))
+    }
+
     pub fn to_owned_image(self) -> OwnedImage {
         let mut data = Vec::with_capacity((self.width() * self.height()) as usize);
         for y in self.y1..self.y2 {
@@ -225,6 +356,18 @@ impl<'a> Image<'a> {
         bytes
     }
 
+    /// Same as [`Image::get_bytes`] with an opaque alpha channel (RGBA, 4 bytes per pixel).
+    pub fn to_rgba(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity((self.width() * self.height() * 4) as usize);
+        for y in self.y1..self.y2 {
+            for x in self.x1..self.x2 {
+                let clr = self.pixel(x, y);
+                bytes.extend_from_slice(&[clr.r, clr.g, clr.b, 255]);
+            }
+        }
+        bytes
+    }
+
     pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
         let bytes = self.get_bytes();
         let img = image::RgbImage::from_raw(self.width(), self.height(), bytes)
@@ -263,15 +406,19 @@ impl<'a> Image<'a> {
     }
 
     /// Gets a subimage with the same height and provided width aligned in the center with both sides trimmed.
+    ///
+    /// The width is clamped to the image and rounded down to an even number so
+    /// it splits evenly around the center; widths below 2 are kept as-is (a 1px
+    /// or empty slice), never inverted. With an odd image width the extra column
+    /// is trimmed from the right.
     pub fn trimmed_centerh(&self, width: u32) -> Self {
-        let size = width.min(self.width());
-        let size = (size >> 1) << 1; // make number even (prevents off-by-one splits)
+        let size = centered_size(width, self.width());
         let spacing = (self.width() - size) / 2;
 
         Self {
             x1: self.x1 + spacing,
             y1: self.y1,
-            x2: self.x2 - spacing,
+            x2: self.x1 + spacing + size,
             y2: self.y2,
             true_width: self.true_width,
             data: self.data,
@@ -307,16 +454,17 @@ impl<'a> Image<'a> {
     }
 
     /// Gets a subimage with the same width and provided height aligned in the center with both sides trimmed.
+    ///
+    /// Same size rules as [`Image::trimmed_centerh`].
     pub fn trimmed_centerv(&self, height: u32) -> Self {
-        let size = height.min(self.height());
-        let size = (size >> 1) << 1; // make number even
+        let size = centered_size(height, self.height());
         let spacing = (self.height() - size) / 2;
 
         Self {
             x1: self.x1,
             y1: self.y1 + spacing,
             x2: self.x2,
-            y2: self.y2 - spacing,
+            y2: self.y1 + spacing + size,
             true_width: self.true_width,
             data: self.data,
         }
@@ -339,6 +487,50 @@ impl<'a> Image<'a> {
         }
     }
 
+    /// Create an arbitrary subimage (relative coordinates), or `None` if the
+    /// rect is empty or doesn't fit inside this image.
+    ///
+    /// Detection code should prefer this over [`Image::sub_image`] so a
+    /// miscomputed crop fails visibly instead of silently shrinking.
+    pub fn try_sub_image(&self, x: u32, y: u32, width: u32, height: u32) -> Option<Self> {
+        let fits = width > 0
+            && height > 0
+            && x.checked_add(width).is_some_and(|right| right <= self.width())
+            && y.checked_add(height).is_some_and(|bottom| bottom <= self.height());
+        fits.then(|| self.sub_image(x, y, width, height))
+    }
+
+    /// [`Image::try_sub_image`] from a [`Rect`].
+    #[inline]
+    pub fn try_sub_image_rect(&self, rect: crate::Rect) -> Option<Self> {
+        self.try_sub_image(rect.x, rect.y, rect.w, rect.h)
+    }
+
+    /// Create a subimage from a [`Rect`] (relative coordinates, same clamping as [`Image::sub_image`]).
+    #[inline]
+    pub fn sub_image_rect(&self, rect: crate::Rect) -> Self {
+        self.sub_image(rect.x, rect.y, rect.w, rect.h)
+    }
+
+    /// Number of pixels per luma value ([`Color::luma`]).
+    ///
+    /// Useful to check whether a crop is bimodal (text on background) before
+    /// binarizing it; [`Image::otsu`] is computed from this.
+    pub fn luma_histogram(&self) -> [u32; 256] {
+        let mut hist = [0u32; 256];
+        for y in self.y1..self.y2 {
+            for x in self.x1..self.x2 {
+                hist[self.pixel(x, y).luma() as usize] += 1;
+            }
+        }
+        hist
+    }
+
+    /// Otsu split of the crop, as used by the Otsu OCR candidate (see [`Otsu`]).
+    pub fn otsu(&self) -> Otsu {
+        otsu_equalized(&self.luma_histogram())
+    }
+
     pub fn average_color(&self) -> Color {
         let mut r = 0u32;
         let mut g = 0u32;
@@ -432,63 +624,357 @@ impl<'a> Image<'a> {
     ///
     /// The function tries multiple preprocessing strategies (adaptive threshold,
     /// Otsu threshold, theme-guided) and picks the most plausible result.
+    ///
+    /// Glyph dilation is taken from `WFBUDDY_OCR_DILATE` (see [`Dilation::from_env`]).
     pub fn get_text(&self, theme: crate::Theme, ocr: &crate::ocr::Ocr) -> String {
-        use imageproc::contrast::{adaptive_threshold, equalize_histogram, otsu_level, threshold, ThresholdType};
+        self.get_text_dilated(theme, ocr, Dilation::from_env())
+    }
 
-        // Upscale small crops – OCR generally performs better on larger glyphs.
-        let mut base = self.to_owned_image();
-        const MIN_H: u32 = 80;
-        if base.height < MIN_H {
-            base = base.resized_h(MIN_H);
-        }
+    /// Same as [`Image::get_text`], also returning how plausible the winning read is.
+    pub fn read_text(&self, theme: crate::Theme, ocr: &crate::ocr::Ocr) -> TextRead {
+        self.read_text_dilated(theme, ocr, Dilation::from_env())
+    }
 
-        // Candidate 1: adaptive threshold (handles gradients/transparency).
-        let adaptive = {
-            let gray = equalize_histogram(&base.to_gray_image());
-            let bin = adaptive_threshold(&gray, 7, 10);
-            OwnedImage::from_gray_as_rgb(&ensure_dark_text_on_light(bin))
-        };
+    /// Same as [`Image::get_text`], with an explicit glyph dilation.
+    ///
+    /// When a dilation is given, each thresholded candidate is also tried in a
+    /// dilated variant and the scoring picks whichever reads better, so thin
+    /// fonts are not forced through a kernel that merges their glyphs.
+    pub fn get_text_dilated(&self, theme: crate::Theme, ocr: &crate::ocr::Ocr, dilation: Option<Dilation>) -> String {
+        self.read_text_dilated(theme, ocr, dilation).text
+    }
 
-        // Candidate 2: global Otsu.
-        let otsu = {
-            let gray = equalize_histogram(&base.to_gray_image());
-            let level = otsu_level(&gray);
-            let bin = threshold(&gray, level, ThresholdType::Binary);
-            OwnedImage::from_gray_as_rgb(&ensure_dark_text_on_light(bin))
+    fn read_text_dilated(&self, theme: crate::Theme, ocr: &crate::ocr::Ocr, dilation: Option<Dilation>) -> TextRead {
+        let base = self.ocr_base();
+
+        // Candidate 1: adaptive threshold (handles gradients/transparency).
+        // Candidate 2: global Otsu, skipped when the crop isn't bimodal (e.g. an
+        // empty background region) since it would only binarize noise into glyphs.
+        let bimodal = base.as_image().otsu().separability >= MIN_OTSU_SEPARABILITY;
+        let mut modes = Vec::with_capacity(5);
+        let mut push = |mode: fn(Option<Dilation>) -> PrepMode| {
+            if let Some(dilation) = dilation {
+                modes.push(mode(Some(dilation)));
+            }
+            modes.push(mode(None));
         };
+        push(PrepMode::Adaptive);
+        if bimodal {
+            push(PrepMode::Otsu);
+        }
 
         // Candidate 3: theme-guided (fallback).
-        let theme_bin = {
-            let mut img = base.clone();
-            img.map_pixels(|v| {
-                let d1 = v.deviation(theme.primary);
-                let d2 = v.deviation(theme.secondary);
-                *v = if d1 < d2 { Color::WHITE } else { Color::BLACK };
-            });
-            img
-        };
+        modes.push(PrepMode::Theme(theme));
 
-        let mut best = String::new();
-        let mut best_score = i64::MIN;
+        let mut best = TextRead {
+            text: String::new(),
+            score: i64::MIN,
+            confidence: 0.0,
+            prep: PrepMode::Theme(theme),
+        };
 
-        for cand in [adaptive, otsu, theme_bin] {
-            let text = ocr.get_text(cand.as_image());
-            let score = score_ocr_text(&text);
-            if score > best_score {
-                best_score = score;
-                best = text;
+        for mode in modes {
+            let read = read_prepared(&base, ocr, mode);
+            if read.score > best.score {
+                best = read;
             }
         }
 
         // Optional debug snapshots.
         if std::env::var("WFBUDDY_WRITE_IMAGE").as_deref() == Ok("1") {
-            if let Some(name) = best.chars().filter(|c| c.is_ascii_alphanumeric()).take(40).collect::<String>().get(0..) {
+            if let Some(name) = best.text.chars().filter(|c| c.is_ascii_alphanumeric()).take(40).collect::<String>().get(0..) {
                 let _ = self.save_png(format!("./debug_ocr_{}.png", name));
             }
         }
 
         best
     }
+
+    /// Extract text with a single, known preprocessing path.
+    ///
+    /// Skips the candidate search of [`Image::get_text`], e.g. when the
+    /// [`TextRead::prep`] that won on a previous frame of the same screen is
+    /// reused. Callers should fall back to [`Image::read_text`] when the
+    /// returned confidence drops.
+    pub fn get_text_prebinarized(&self, ocr: &crate::ocr::Ocr, prep: PrepMode) -> String {
+        self.read_text_prebinarized(ocr, prep).text
+    }
+
+    /// Same as [`Image::get_text_prebinarized`], also returning score and confidence.
+    pub fn read_text_prebinarized(&self, ocr: &crate::ocr::Ocr, prep: PrepMode) -> TextRead {
+        read_prepared(&self.ocr_base(), ocr, prep)
+    }
+
+    /// Owned copy of the crop, upscaled for OCR.
+    fn ocr_base(&self) -> OwnedImage {
+        // Upscale small crops – OCR generally performs better on larger glyphs.
+        // The target grows with the capture resolution (80px at 1080p, capped at
+        // 160px), so high-DPI crops whose text is physically small still get
+        // resolved instead of being left at the 1080p target.
+        let mut base = self.to_owned_image();
+        const MIN_H: u32 = 80;
+        const MAX_H: u32 = 160;
+        let target_h = (MIN_H * self.source_height() / 1080).clamp(MIN_H, MAX_H);
+        if base.width > 0 && base.height > 0 && base.height < target_h {
+            base = base.resized_h(target_h);
+        }
+        base
+    }
+}
+
+/// Binarize `base` with `mode` and OCR it.
+fn read_prepared(base: &OwnedImage, ocr: &crate::ocr::Ocr, mode: PrepMode) -> TextRead {
+    use imageproc::contrast::{adaptive_threshold, equalize_histogram, threshold, ThresholdType};
+
+    let binarized = |bin: image::GrayImage, dilation: Option<Dilation>| {
+        let bin = ensure_dark_text_on_light(bin);
+        match dilation {
+            Some(dilation) => OwnedImage::from_gray_as_rgb(&dilate_binary(&bin, dilation)),
+            None => OwnedImage::from_gray_as_rgb(&bin),
+        }
+    };
+
+    let cand = match mode {
+        PrepMode::Adaptive(dilation) => {
+            let gray = equalize_histogram(&base.to_gray_image());
+            binarized(adaptive_threshold(&gray, 7, 10), dilation)
+        }
+        PrepMode::Otsu(dilation) => {
+            let otsu = base.as_image().otsu();
+            binarized(threshold(&base.to_gray_image(), otsu.level, ThresholdType::Binary), dilation)
+        }
+        PrepMode::Theme(theme) => binarize_theme(base, theme, ColorMetric::from_env()),
+    };
+
+    let (text, confidence) = ocr.get_text_with_confidence(cand.as_image());
+    let score = score_ocr_text(&text);
+    TextRead { text, score, confidence, prep: mode }
+}
+
+/// A single OCR preprocessing path (see [`Image::get_text_prebinarized`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrepMode {
+    /// Histogram equalization plus adaptive threshold, optionally dilated.
+    Adaptive(Option<Dilation>),
+    /// Global Otsu threshold picked on the equalized histogram (see [`Image::otsu`]), optionally dilated.
+    Otsu(Option<Dilation>),
+    /// Pixels classified against the theme colors.
+    Theme(crate::Theme),
+}
+
+/// Size of a centered trim: clamped to `available`, rounded down to even from 2 up.
+fn centered_size(requested: u32, available: u32) -> u32 {
+    let size = requested.min(available);
+    if size < 2 { size } else { size & !1 }
+}
+
+/// Winning OCR candidate of [`Image::read_text`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRead {
+    pub text: String,
+    /// Plausibility heuristic the candidates were ranked by (higher is better).
+    pub score: i64,
+    /// Mean recognition confidence of the text lines (0..1, 0 when nothing was read).
+    pub confidence: f32,
+    /// Preprocessing path that produced this read.
+    pub prep: PrepMode,
+}
+
+/// Color distance used when classifying pixels against the theme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorMetric {
+    /// [`Color::deviation`] (RGB space).
+    #[default]
+    Deviation,
+    /// [`Color::delta_e`] (OkLab); separates colors that differ mostly in hue.
+    Perceptual,
+}
+
+impl ColorMetric {
+    /// The metric set by `WFBUDDY_OCR_COLOR_METRIC` (read once), `perceptual` or `deviation`.
+    pub fn from_env() -> Self {
+        *COLOR_METRIC
+    }
+
+    fn distance(&self, a: Color, b: Color) -> f32 {
+        match self {
+            Self::Deviation => a.deviation(b),
+            Self::Perceptual => a.delta_e(b),
+        }
+    }
+}
+
+static COLOR_METRIC: LazyLock<ColorMetric> =
+    LazyLock::new(|| match std::env::var("WFBUDDY_OCR_COLOR_METRIC").as_deref() {
+        Ok("perceptual") => ColorMetric::Perceptual,
+        _ => ColorMetric::Deviation,
+    });
+
+/// Classify every pixel as text (closer to `theme.primary`) or background.
+fn binarize_theme(img: &OwnedImage, theme: crate::Theme, metric: ColorMetric) -> OwnedImage {
+    let mut img = img.clone();
+    img.map_pixels(|v| {
+        let d1 = metric.distance(*v, theme.primary);
+        let d2 = metric.distance(*v, theme.secondary);
+        *v = if d1 < d2 { Color::WHITE } else { Color::BLACK };
+    });
+    img
+}
+
+/// Minimum [`Otsu::separability`] for a crop to be treated as text on background.
+///
+/// A unimodal (gaussian-like) crop lands around 0.64, clean text is usually above 0.8.
+const MIN_OTSU_SEPARABILITY: f32 = 0.7;
+
+/// Otsu's split of a luma histogram (see [`Image::otsu`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Otsu {
+    /// Luma threshold: values above it are one class, the rest the other.
+    pub level: u8,
+    /// How bimodal the histogram is: the between-class variance at `level`
+    /// divided by the total variance (0..=1). Close to 1 for a cleanly bimodal
+    /// crop, 0 for a flat one.
+    pub separability: f32,
+}
+
+/// Otsu's method: the level maximizing the between-class variance.
+fn otsu(hist: &[u32; 256]) -> Otsu {
+    let total = hist.iter().map(|&n| n as f64).sum::<f64>();
+    if total == 0.0 {
+        return Otsu { level: 0, separability: 0.0 };
+    }
+
+    let sum: f64 = hist.iter().enumerate().map(|(i, &n)| i as f64 * n as f64).sum();
+    let mean = sum / total;
+    let variance: f64 = hist
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| (i as f64 - mean).powi(2) * n as f64)
+        .sum::<f64>()
+        / total;
+
+    let mut level = 0u8;
+    let mut best_between = 0.0f64;
+    let mut weight_b = 0.0f64;
+    let mut sum_b = 0.0f64;
+    for (i, &n) in hist.iter().enumerate() {
+        weight_b += n as f64;
+        if weight_b == 0.0 {
+            continue;
+        }
+        let weight_f = total - weight_b;
+        if weight_f == 0.0 {
+            break;
+        }
+
+        sum_b += i as f64 * n as f64;
+        let mean_b = sum_b / weight_b;
+        let mean_f = (sum - sum_b) / weight_f;
+        let between = weight_b * weight_f * (mean_b - mean_f).powi(2) / (total * total);
+        if between > best_between {
+            best_between = between;
+            level = i as u8;
+        }
+    }
+
+    // A (nearly) flat crop has no meaningful split.
+    let separability = if variance < 1.0 { 0.0 } else { (best_between / variance) as f32 };
+    Otsu { level, separability }
+}
+
+/// Otsu split with the level picked on the histogram-equalized crop, mapped
+/// back to a raw luma value.
+///
+/// Equalization changes where the split falls on low-contrast crops. As it
+/// is monotonic, thresholding the raw crop at the returned level binarizes
+/// exactly like thresholding the equalized crop at its own Otsu level, and
+/// the level can be read off [`Image::luma_histogram`] directly.
+///
+/// The separability is that of the raw histogram: equalizing spreads any
+/// noisy crop to a roughly uniform histogram (separability ~0.75), text and
+/// empty background alike, which would leave nothing to gate on.
+fn otsu_equalized(hist: &[u32; 256]) -> Otsu {
+    // Same mapping as `imageproc::contrast::equalize_histogram`.
+    let total = hist.iter().map(|&n| n as f32).sum::<f32>().max(1.0);
+    let mut lut = [0u8; 256];
+    let mut cumulative = 0u32;
+    for (v, &n) in hist.iter().enumerate() {
+        cumulative += n;
+        lut[v] = (255.0 * (cumulative as f32 / total)).min(255.0) as u8;
+    }
+
+    let mut equalized = [0u32; 256];
+    for (v, &n) in hist.iter().enumerate() {
+        equalized[lut[v] as usize] += n;
+    }
+
+    // Equalized values at or below the level are a prefix of the raw ones.
+    let equalized_level = otsu(&equalized).level;
+    let level = (0..=255u8).rev().find(|&v| lut[v as usize] <= equalized_level).unwrap_or(0);
+    Otsu {
+        level,
+        separability: otsu(hist).separability,
+    }
+}
+
+/// Shape of the structuring element used by [`dilate_binary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DilationShape {
+    /// Full `(2r+1)×(2r+1)` square; strongest, can merge adjacent glyphs.
+    Square,
+    /// Diamond/cross (L1 norm); thickens strokes while keeping gaps open longer.
+    Cross,
+}
+
+/// Glyph dilation applied to binarized OCR candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dilation {
+    pub radius: u8,
+    pub shape: DilationShape,
+}
+
+impl Dilation {
+    /// The dilation set by `WFBUDDY_OCR_DILATE` (read once), e.g. `square1`, `cross2` or `none`.
+    ///
+    /// Unset or unparsable values disable dilation.
+    pub fn from_env() -> Option<Self> {
+        *DILATION
+    }
+}
+
+static DILATION: LazyLock<Option<Dilation>> =
+    LazyLock::new(|| std::env::var("WFBUDDY_OCR_DILATE").ok()?.parse().ok());
+
+impl std::str::FromStr for Dilation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_ascii_lowercase();
+        let (shape, radius) = if let Some(r) = s.strip_prefix("square") {
+            (DilationShape::Square, r)
+        } else if let Some(r) = s.strip_prefix("cross") {
+            (DilationShape::Cross, r)
+        } else {
+            anyhow::bail!("unknown dilation {s:?} (expected squareN or crossN)");
+        };
+
+        let radius = radius.parse::<u8>().context("dilation radius")?;
+        anyhow::ensure!(radius > 0, "dilation radius must be at least 1");
+        Ok(Self { radius, shape })
+    }
+}
+
+/// Thicken the dark text strokes of a dark-on-light binary image.
+///
+/// Text is black here, so growing it is an erosion of the white background.
+fn dilate_binary(bin: &image::GrayImage, dilation: Dilation) -> image::GrayImage {
+    use imageproc::distance_transform::Norm;
+
+    let norm = match dilation.shape {
+        DilationShape::Square => Norm::LInf,
+        DilationShape::Cross => Norm::L1,
+    };
+    imageproc::morphology::erode(bin, norm, dilation.radius)
 }
 
 fn ensure_dark_text_on_light(mut bin: image::GrayImage) -> image::GrayImage {
@@ -544,6 +1030,32 @@ impl Color {
         Self { r, g, b }
     }
 
+    /// Parse a `#RRGGBB` or `RRGGBB` hex string.
+    pub fn from_hex(s: &str) -> Option<Self> {
+        let hex = s.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        // from_str_radix alone would also take a sign ("+1")
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Self::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    /// Format as `#RRGGBB` (uppercase).
+    pub fn to_hex(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+
+    /// Alpha-blend `over` on top of this color (`alpha` 0 keeps this color,
+    /// 1 gives `over`).
+    pub fn blend(&self, over: Color, alpha: f32) -> Color {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * alpha).round() as u8;
+        Color::new(mix(self.r, over.r), mix(self.g, over.g), mix(self.b, over.b))
+    }
+
     /// Deviation metric used throughout the project for UI color checks.
     ///
     /// NOTE: This is intentionally *not* Euclidean distance; it is tuned for
@@ -556,6 +1068,62 @@ impl Color {
             .powi(3)
     }
 
+    /// Approximate perceptual distance (Euclidean distance in OkLab).
+    ///
+    /// Unlike [`Color::deviation`] this weighs hue differences the way the eye
+    /// does, so a saturated accent color is not mistaken for a gray of similar
+    /// brightness. Identical colors give 0.0, black vs white gives 1.0.
+    pub fn delta_e(&self, other: Color) -> f32 {
+        let [l1, a1, b1] = self.to_oklab();
+        let [l2, a2, b2] = other.to_oklab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    fn to_oklab(self) -> [f32; 3] {
+        fn linear(c: u8) -> f32 {
+            let c = c as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        let (r, g, b) = (linear(self.r), linear(self.g), linear(self.b));
+
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+        [
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        ]
+    }
+
+    /// Hue (degrees, 0..360), saturation and value (0..1).
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        (hue, saturation, max)
+    }
+
     /// Compute luma (grayscale intensity).
     pub fn luma(&self) -> u8 {
         let r = self.r as u32;
@@ -564,3 +1132,80 @@ impl Color {
         ((299 * r + 587 * g + 114 * b) / 1000) as u8
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex_valid() {
+        assert_eq!(Color::from_hex("#10161F"), Some(Color::new(16, 22, 31)));
+        assert_eq!(Color::from_hex("10161f"), Some(Color::new(16, 22, 31)));
+        assert_eq!(Color::from_hex("  #FFffFF "), Some(Color::new(255, 255, 255)));
+        assert_eq!(Color::from_hex(&Color::new(1, 2, 3).to_hex()), Some(Color::new(1, 2, 3)));
+    }
+
+    #[test]
+    fn from_hex_wrong_length() {
+        assert_eq!(Color::from_hex("#FFF"), None);
+        assert_eq!(Color::from_hex("#FFFFF"), None);
+        assert_eq!(Color::from_hex("#FFFFFFF"), None);
+        assert_eq!(Color::from_hex(""), None);
+    }
+
+    #[test]
+    fn from_hex_rejects_signs() {
+        assert_eq!(Color::from_hex("#+1+2+3"), None);
+        assert_eq!(Color::from_hex("-1-2-3"), None);
+    }
+
+    #[test]
+    fn from_hex_rejects_non_hex() {
+        assert_eq!(Color::from_hex("#GGHHII"), None);
+        assert_eq!(Color::from_hex("#12 456"), None);
+        assert_eq!(Color::from_hex("#ÄÄÄ"), None);
+    }
+
+    /// Low-contrast "text": a noisy dark background with a brighter band.
+    fn noisy_text_crop() -> OwnedImage {
+        let (w, h) = (120u32, 40u32);
+        let mut seed = 0x2545_f491u32;
+        let mut bytes = Vec::with_capacity((w * h * 4) as usize);
+        for y in 0..h {
+            for x in 0..w {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let noise = (seed >> 28) as u8;
+                let glyph = (15..25).contains(&y) && x % 12 < 5;
+                let v = if glyph { 70 } else { 40 } + noise;
+                bytes.extend_from_slice(&[v, v, v, 255]);
+            }
+        }
+        OwnedImage::from_rgba(w as usize, &bytes)
+    }
+
+    #[test]
+    fn otsu_splits_two_clusters() {
+        let mut hist = [0u32; 256];
+        hist[30] = 900;
+        hist[200] = 100;
+
+        let split = otsu(&hist);
+        assert!((30..200).contains(&split.level));
+        assert!(split.separability > 0.99);
+        assert_eq!(otsu(&[0; 256]).separability, 0.0);
+    }
+
+    #[test]
+    fn otsu_level_matches_the_equalized_threshold() {
+        use imageproc::contrast::{equalize_histogram, otsu_level, threshold, ThresholdType};
+
+        let img = noisy_text_crop();
+        let gray = img.to_gray_image();
+        let equalized = equalize_histogram(&gray);
+        let expected = threshold(&equalized, otsu_level(&equalized), ThresholdType::Binary);
+
+        let split = img.as_image().otsu();
+        assert_eq!(threshold(&gray, split.level, ThresholdType::Binary), expected);
+        assert!(split.separability >= MIN_OTSU_SEPARABILITY);
+    }
+}
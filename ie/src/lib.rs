@@ -2,35 +2,210 @@
 //!
 //! This crate contains the computer vision and OCR logic. The higher-level UI
 //! (iced) and application state live in the `wfbuddy` crate.
+//!
+//! Detection is deterministic: for the same image, theme and settings every
+//! detector returns the same result, so a saved PNG reproduces a detection bug
+//! exactly (see [`Ie::analyze_file`]). Sampling uses fixed strides and there is
+//! no randomness or iteration-order dependence; keep it that way.
+
+mod debug;
+mod geom;
+pub use geom::*;
 
 mod image;
 pub use image::*;
 
 mod ocr;
+pub use ocr::{Ocr, OcrAssetSource};
 pub mod screen;
 pub mod util;
 
 mod theme;
 pub use theme::*;
 
+/// Full detection result for a single capture (see [`Ie::analyze`]).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Analysis {
+    pub rewards: screen::relicreward::Rewards,
+    pub selected: Option<usize>,
+    pub party_header: Option<String>,
+    /// Mission type read from the in-mission HUD (not shown on the reward screen).
+    pub mission_type: Option<util::MissionType>,
+}
+
+impl Analysis {
+    /// Serialize as pretty-printed JSON.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Reward screen bundled for [`Ie::self_test`]: four slots at 1280×720 with
+/// light text, read with [`Theme::WHITE`].
+const SELF_TEST_SAMPLE: &[u8] = include_bytes!("asset/selftest_reward.png");
+/// Reward names shown in [`SELF_TEST_SAMPLE`], left to right.
+const SELF_TEST_NAMES: [&str; 4] = ["Forma Blueprint", "Lex Prime Barrel", "Braton Prime Stock", "Paris Prime Grip"];
+
+/// Outcome of [`Ie::self_test`]: which pipeline stages produced the expected result.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct SelfTestReport {
+    /// Where the OCR models were loaded from (`None` if OCR is unavailable).
+    pub ocr_asset_source: Option<OcrAssetSource>,
+    /// Why OCR is unavailable, if the models failed to load.
+    pub ocr_error: Option<String>,
+    /// Why the sample couldn't be decoded; nothing else ran.
+    pub decode_error: Option<String>,
+    /// Reward slots found, out of [`SelfTestReport::expected`].
+    pub slots: usize,
+    /// Reward slots in the sample.
+    pub expected: usize,
+    /// Expected names that were read wrong, with what was read instead.
+    pub misread: Vec<(String, String)>,
+}
+
+impl SelfTestReport {
+    /// Every stage worked: OCR loaded, all slots found and every name read correctly.
+    pub fn passed(&self) -> bool {
+        self.ocr_error.is_none()
+            && self.decode_error.is_none()
+            && self.slots == self.expected
+            && self.misread.is_empty()
+    }
+}
+
+impl std::fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Self-test: {}", if self.passed() { "passed" } else { "FAILED" })?;
+        match (&self.ocr_asset_source, &self.ocr_error) {
+            (_, Some(err)) => writeln!(f, "  OCR: unavailable ({err})")?,
+            (Some(source), None) => writeln!(f, "  OCR: {source}")?,
+            (None, None) => writeln!(f, "  OCR: unavailable")?,
+        }
+        if let Some(err) = &self.decode_error {
+            return write!(f, "  sample: {err}");
+        }
+        write!(f, "  slots: {}/{}", self.slots, self.expected)?;
+        for (expected, read) in &self.misread {
+            write!(f, "\n  misread: {expected:?} as {read:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Default for the OCR engine's minimum result confidence.
+///
+/// Lines below it are dropped before [`Image::get_text`] scores its candidates,
+/// so raising it makes noisy candidates lose outright (clean captures), while
+/// lowering it lets more partial reads compete (blurry or streamed captures).
+pub const DEFAULT_MIN_CONFIDENCE: f32 = 0.5;
+
+/// Default per-channel threshold of the blank frame check (see [`OwnedImage::is_blank`]).
+///
+/// Captures that are blank by this measure skip detection entirely.
+pub const DEFAULT_BLANK_THRESHOLD: u8 = 8;
+
 /// Computer vision engine.
 ///
 /// Owns an OCR engine and a sampled UI theme.
+///
+/// If the OCR models fail to load the engine runs in a degraded mode: OCR-based
+/// methods return empty results while the cheap detectors (reward screen,
+/// selection, theme sampling) keep working.
 pub struct Ie {
-    ocr: crate::ocr::Ocr,
+    ocr: Option<crate::ocr::Ocr>,
+    ocr_error: Option<String>,
     theme: Theme,
+    blank_threshold: u8,
+    selection_probe: screen::relicreward::SelectionProbe,
+    stackable_names: Vec<String>,
 }
 
 impl Ie {
     /// Create a new engine instance.
+    ///
+    /// Arguments are the OCR model files (detection model, recognition model,
+    /// charset), the OCR minimum confidence and the UI theme. Fails if the OCR
+    /// models can't be loaded.
+    pub fn try_new(
+        detection: impl AsRef<std::path::Path>,
+        recognition: impl AsRef<std::path::Path>,
+        charsset: impl AsRef<std::path::Path>,
+        min_confidence: f32,
+        theme: Theme,
+    ) -> anyhow::Result<Self> {
+        let ocr = crate::ocr::Ocr::try_new(detection, recognition, charsset, min_confidence)?;
+        Ok(Self::with_ocr(ocr, theme))
+    }
+
+    /// Create an engine around an already constructed OCR engine
+    /// (e.g. [`Ocr::null`] with the `null-ocr` feature).
+    pub fn with_ocr(ocr: Ocr, theme: Theme) -> Self {
+        Self {
+            ocr: Some(ocr),
+            ocr_error: None,
+            theme,
+            blank_threshold: DEFAULT_BLANK_THRESHOLD,
+            selection_probe: Default::default(),
+            stackable_names: Vec::new(),
+        }
+    }
+
+    /// Same as [`Ie::try_new`], panicking if the OCR models can't be loaded.
     pub fn new(
         detection: impl AsRef<std::path::Path>,
         recognition: impl AsRef<std::path::Path>,
         charsset: impl AsRef<std::path::Path>,
+        min_confidence: f32,
         theme: Theme,
     ) -> Self {
-        let ocr = crate::ocr::Ocr::new(detection, recognition, charsset);
-        Self { ocr, theme }
+        Self::try_new(detection, recognition, charsset, min_confidence, theme).expect("failed to load OCR models")
+    }
+
+    /// Same as [`Ie::try_new`], but falls back to running without OCR instead of failing.
+    ///
+    /// Check [`Ie::ocr_error`] to find out whether OCR is available.
+    pub fn new_lenient(
+        detection: impl AsRef<std::path::Path>,
+        recognition: impl AsRef<std::path::Path>,
+        charsset: impl AsRef<std::path::Path>,
+        min_confidence: f32,
+        theme: Theme,
+    ) -> Self {
+        Self::try_new(detection, recognition, charsset, min_confidence, theme).unwrap_or_else(|err| Self {
+            ocr: None,
+            ocr_error: Some(format!("{err:#}")),
+            theme,
+            blank_threshold: DEFAULT_BLANK_THRESHOLD,
+            selection_probe: Default::default(),
+            stackable_names: Vec::new(),
+        })
+    }
+
+    /// Rebuild the OCR engine from new model files (e.g. after a language change)
+    /// or with a new minimum confidence.
+    ///
+    /// On error the previous engine is kept and the error is returned.
+    pub fn set_ocr_assets(
+        &mut self,
+        detection: impl AsRef<std::path::Path>,
+        recognition: impl AsRef<std::path::Path>,
+        charsset: impl AsRef<std::path::Path>,
+        min_confidence: f32,
+    ) -> anyhow::Result<()> {
+        let ocr = crate::ocr::Ocr::try_new(detection, recognition, charsset, min_confidence)?;
+        self.ocr = Some(ocr);
+        self.ocr_error = None;
+        Ok(())
+    }
+
+    /// Where the OCR models were loaded from (`None` if they failed to load).
+    pub fn ocr_asset_source(&self) -> Option<&OcrAssetSource> {
+        self.ocr.as_ref().map(|ocr| ocr.source())
+    }
+
+    /// Why OCR is unavailable, if the models failed to load.
+    pub fn ocr_error(&self) -> Option<&str> {
+        self.ocr_error.as_deref()
     }
 
     /// Replace the current UI theme (useful when re-sampling from the options menu).
@@ -42,19 +217,215 @@ impl Ie {
         self.theme
     }
 
+    /// Set the per-channel threshold below which a capture counts as blank
+    /// (default [`DEFAULT_BLANK_THRESHOLD`]). Raise it if black frames with a
+    /// noisy encoder still get analyzed, lower it if very dark scenes get skipped.
+    pub fn set_blank_threshold(&mut self, threshold: u8) {
+        self.blank_threshold = threshold;
+    }
+
+    /// Set where the selection highlight is probed within a reward slot, for
+    /// UI layouts that draw it elsewhere than the default top-right corner.
+    pub fn set_selection_probe(&mut self, probe: screen::relicreward::SelectionProbe) {
+        self.selection_probe = probe;
+    }
+
+    /// Set the (client language) names of rewards that can drop stacked, like
+    /// the forma blueprint. Only these get their stack badge read; without
+    /// any every reward counts as a single item.
+    pub fn set_stackable_names(&mut self, names: Vec<String>) {
+        self.stackable_names = names;
+    }
+
     /// Detect relic rewards and timer from a screen capture.
-    pub fn relicreward_get_rewards(&self, img: &OwnedImage) -> screen::relicreward::Rewards {
+    ///
+    /// `ui_scale` is the in-game HUD scale (1.0 = 100%).
+    pub fn relicreward_get_rewards(&self, img: &OwnedImage, ui_scale: f32) -> screen::relicreward::Rewards {
+        let Some(ocr) = &self.ocr else {
+            return screen::relicreward::Rewards::default();
+        };
+        if img.is_blank(self.blank_threshold) {
+            return screen::relicreward::Rewards::default();
+        }
         // The detection logic is resolution-independent, so we avoid resizing here.
-        screen::relicreward::get_rewards(img.as_image(), self.theme, &self.ocr)
+        screen::relicreward::get_rewards(img.as_image(), ui_scale, self.theme, ocr, &self.stackable_names)
+    }
+
+    /// Detect rewards, selection and slot geometry with a single slot detection pass.
+    ///
+    /// Prefer this over calling [`Ie::relicreward_get_rewards`] and
+    /// [`Ie::relicreward_get_selected`] on the same frame.
+    pub fn relicreward_analyze(&self, img: &OwnedImage, ui_scale: f32) -> screen::relicreward::RelicAnalysis {
+        self.relicreward_analyze_hinted(img, ui_scale, None)
+    }
+
+    /// [`Ie::relicreward_analyze`] that first searches around the slots of a
+    /// previous frame (see [`screen::relicreward::SlotHint::from_slots`]).
+    ///
+    /// The hint is passed in rather than remembered, so the same image and hint
+    /// always give the same result.
+    pub fn relicreward_analyze_hinted(
+        &self,
+        img: &OwnedImage,
+        ui_scale: f32,
+        hint: Option<screen::relicreward::SlotHint>,
+    ) -> screen::relicreward::RelicAnalysis {
+        if img.is_blank(self.blank_threshold) {
+            return screen::relicreward::RelicAnalysis::default();
+        }
+        screen::relicreward::analyze(
+            img.as_image(),
+            ui_scale,
+            self.theme,
+            self.ocr.as_ref(),
+            hint,
+            self.selection_probe,
+            &self.stackable_names,
+        )
+    }
+
+    /// Cheap check (no OCR) whether the capture shows the relic reward screen.
+    pub fn relicreward_is_screen(&self, img: &OwnedImage, ui_scale: f32) -> bool {
+        screen::relicreward::is_screen(img.as_image(), ui_scale)
+    }
+
+    /// Estimate the in-game HUD scale from a capture of the relic reward screen
+    /// (`None` if it isn't showing).
+    pub fn estimate_ui_scale(&self, img: &OwnedImage) -> Option<f32> {
+        screen::relicreward::estimate_ui_scale(img.as_image())
+    }
+
+    /// Check the current theme against the reward text of a reward screen capture
+    /// (`None` if it isn't showing, see [`screen::relicreward::validate_theme`]).
+    pub fn validate_theme_against(&self, img: &OwnedImage) -> Option<screen::relicreward::ThemeValidation> {
+        screen::relicreward::validate_theme(img.as_image(), self.theme)
+    }
+
+    /// Classify the rarity bead of a reward slot (see [`screen::relicreward::classify_rarity`]).
+    pub fn classify_rarity(
+        &self,
+        img: &OwnedImage,
+        slot: Rect,
+        tolerances: screen::relicreward::RarityTolerances,
+    ) -> Option<screen::relicreward::Rarity> {
+        screen::relicreward::classify_rarity(img.as_image(), slot, tolerances)
+    }
+
+    /// Reward slot rectangles found by the slot detector (no OCR), left to right.
+    ///
+    /// Useful to tune or check slot geometry independently of OCR quality.
+    pub fn relicreward_detect_slots(&self, img: &OwnedImage, ui_scale: f32) -> Vec<Rect> {
+        screen::relicreward::detect_reward_slots(img.as_image(), ui_scale)
     }
 
     /// Detect which reward slot is currently selected.
-    pub fn relicreward_get_selected(&self, img: &OwnedImage) -> Option<usize> {
-        screen::relicreward::get_selected(img.as_image(), self.theme)
+    pub fn relicreward_get_selected(&self, img: &OwnedImage, ui_scale: f32) -> Option<usize> {
+        screen::relicreward::get_selected(img.as_image(), ui_scale, self.theme, self.selection_probe)
+    }
+
+    /// Check whether the capture shows the end-of-mission summary screen.
+    pub fn missionreward_is_screen(&self, img: &OwnedImage) -> bool {
+        let Some(ocr) = &self.ocr else {
+            return false;
+        };
+        screen::missionreward::is_screen(img.as_image(), self.theme, ocr)
+    }
+
+    /// Read the reward list from the end-of-mission summary screen.
+    pub fn missionreward_get_rewards(&self, img: &OwnedImage) -> screen::missionreward::MissionRewards {
+        let Some(ocr) = &self.ocr else {
+            return screen::missionreward::MissionRewards::default();
+        };
+        screen::missionreward::get_rewards(img.as_image(), self.theme, ocr)
+    }
+
+    /// Run every detector on a capture.
+    ///
+    /// Intended for scripting and regression tests, where the result is
+    /// compared against a labeled screenshot.
+    pub fn analyze(&self, img: &OwnedImage, ui_scale: f32) -> Analysis {
+        let relic = self.relicreward_analyze(img, ui_scale);
+        Analysis {
+            rewards: relic.rewards,
+            selected: relic.selected,
+            party_header: self.util_party_header_text(img, None),
+            mission_type: self.util_mission_type(img),
+        }
+    }
+
+    /// Load a screenshot (PNG, JPEG, ...) from disk and run [`Ie::analyze`] on it.
+    ///
+    /// Also the fallback for systems where screen capture isn't permitted.
+    pub fn analyze_file(&self, path: impl AsRef<std::path::Path>, ui_scale: f32) -> anyhow::Result<Analysis> {
+        use anyhow::Context;
+
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).with_context(|| format!("read {:?}", path))?;
+        let img = OwnedImage::from_encoded(&bytes).with_context(|| format!("decode {:?}", path))?;
+        Ok(self.analyze(&img, ui_scale))
+    }
+
+    /// Run the relic reward pipeline on the bundled reward screen sample and
+    /// compare the result with the names it is known to show.
+    ///
+    /// Meant for a startup/debug check so broken models or detection show up
+    /// before the user reaches a reward screen. Independent of the configured
+    /// theme and HUD scale, the sample has its own.
+    pub fn self_test(&self) -> SelfTestReport {
+        let mut report = SelfTestReport {
+            ocr_asset_source: self.ocr_asset_source().cloned(),
+            ocr_error: self.ocr_error.clone(),
+            expected: SELF_TEST_NAMES.len(),
+            ..Default::default()
+        };
+
+        let img = match OwnedImage::from_encoded(SELF_TEST_SAMPLE) {
+            Ok(img) => img,
+            Err(err) => {
+                report.decode_error = Some(format!("{err:#}"));
+                return report;
+            }
+        };
+
+        let relic = screen::relicreward::analyze(
+            img.as_image(),
+            1.0,
+            Theme::WHITE,
+            self.ocr.as_ref(),
+            None,
+            Default::default(),
+            &[],
+        );
+        report.slots = relic.rects.len();
+        if self.ocr.is_none() {
+            return report;
+        }
+
+        for (i, expected) in SELF_TEST_NAMES.iter().enumerate() {
+            let read = relic.rewards.rewards.get(i).map_or("", |r| r.name.as_str());
+            if !read.eq_ignore_ascii_case(expected) {
+                report.misread.push((expected.to_string(), read.to_string()));
+            }
+        }
+        report
     }
 
     /// Try to OCR the party header text (returns `None` if not found).
-    pub fn util_party_header_text(&self, img: &OwnedImage) -> Option<String> {
-        util::party_header_text(img.as_image(), self.theme, &self.ocr)
+    ///
+    /// `roi` overrides the default avatar-bar search for moved HUD elements.
+    pub fn util_party_header_text(&self, img: &OwnedImage, roi: Option<RelativeRect>) -> Option<String> {
+        util::party_header_text(img.as_image(), self.theme, self.ocr.as_ref()?, roi)
+    }
+
+    /// Try to OCR the name of the relic being opened from the reward screen
+    /// (`None` if it isn't readable).
+    pub fn util_relic_name_text(&self, img: &OwnedImage) -> Option<String> {
+        util::relic_name_text(img.as_image(), self.theme, self.ocr.as_ref()?)
+    }
+
+    /// Try to read the mission type from the in-mission HUD
+    /// (see [`util::mission_type`]).
+    pub fn util_mission_type(&self, img: &OwnedImage) -> Option<util::MissionType> {
+        util::mission_type(img.as_image(), self.theme, self.ocr.as_ref()?)
     }
 }
@@ -4,25 +4,70 @@
 //! OCR engines are sensitive to input quality, so most preprocessing is done
 //! in `Image::get_text(...)` before calling into this module.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 
 pub struct Ocr {
-    engine: ocr_rs::OcrEngine,
+    /// `None` for [`Ocr::null`].
+    engine: Option<ocr_rs::OcrEngine>,
+    source: OcrAssetSource,
+}
+
+/// Where the models of an [`Ocr`] engine came from.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum OcrAssetSource {
+    /// The models shipped with the app (`ocr/` next to the executable).
+    Embedded,
+    /// Models from anywhere else (working directory, custom folder), by the
+    /// path of the recognition model.
+    Disk(PathBuf),
+    /// No models at all ([`Ocr::null`]).
+    Null,
+}
+
+impl OcrAssetSource {
+    /// Classify the recognition model path the engine was loaded from.
+    fn of(recognition: &Path) -> Self {
+        let path = recognition.canonicalize().unwrap_or_else(|_| recognition.to_path_buf());
+        let bundled = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent()?.join("ocr").canonicalize().ok());
+        match bundled {
+            Some(dir) if path.parent() == Some(dir.as_path()) => Self::Embedded,
+            _ => Self::Disk(path),
+        }
+    }
+}
+
+impl std::fmt::Display for OcrAssetSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Embedded => write!(f, "bundled models"),
+            Self::Disk(path) => write!(f, "{}", path.display()),
+            Self::Null => write!(f, "no models (null OCR)"),
+        }
+    }
 }
 
 impl Ocr {
     /// Initialize the OCR engine with the given model paths.
     ///
-    /// This is kept infallible from the perspective of the rest of the codebase
-    /// (errors are mapped to a panic) because failing to load OCR models is a
-    /// hard configuration error for the application.
-    pub fn new(
+    /// `min_confidence` drops recognized lines the engine is less sure about
+    /// (see [`crate::DEFAULT_MIN_CONFIDENCE`]).
+    ///
+    /// Fails if the model files are missing or invalid; callers decide whether
+    /// that is fatal (see `Ie::new_lenient`, which degrades to detection without OCR).
+    pub fn try_new(
         detection: impl AsRef<Path>,
         recognition: impl AsRef<Path>,
         charsset: impl AsRef<Path>,
-    ) -> Self {
+        min_confidence: f32,
+    ) -> anyhow::Result<Self> {
+        // Remember where the language model actually came from, so users can
+        // tell whether their custom `ocr/` folder is picked up.
+        let source = OcrAssetSource::of(recognition.as_ref());
+
         // `ocr-rs` expects an `i32` thread count. Clamp safely in case the
         // platform reports an unusually large value.
         let thread_count: i32 = std::thread::available_parallelism()
@@ -41,27 +86,56 @@ impl Ocr {
                 // small stylized fonts at a CPU cost.
                 precision_mode: ocr_rs::PrecisionMode::High,
                 enable_parallel: thread_count > 1,
-                min_result_confidence: 0.5,
+                min_result_confidence: min_confidence.clamp(0.0, 1.0),
                 ..Default::default()
             }),
         )
-        .context("failed to initialize OCR engine")
-        .expect("OCR engine init failed (missing or invalid model files?)");
+        .context("failed to initialize OCR engine (missing or invalid model files?)")?;
 
-        Self { engine }
+        Ok(Self {
+            engine: Some(engine),
+            source,
+        })
+    }
+
+    /// Engine without models that recognizes nothing (empty text, zero confidence).
+    #[cfg(feature = "null-ocr")]
+    pub fn null() -> Self {
+        Self {
+            engine: None,
+            source: OcrAssetSource::Null,
+        }
+    }
+
+    /// Where the models of this engine were loaded from.
+    pub fn source(&self) -> &OcrAssetSource {
+        &self.source
     }
 
     /// Recognize text from an RGB image view.
     pub fn get_text(&self, image: crate::Image) -> String {
+        self.get_text_with_confidence(image).0
+    }
+
+    /// Recognize text and return it together with the mean line confidence
+    /// (0 when nothing was recognized).
+    pub fn get_text_with_confidence(&self, image: crate::Image) -> (String, f32) {
+        let Some(engine) = &self.engine else {
+            return (String::new(), 0.0);
+        };
         let image = ocr_rs::preprocess::rgb_to_image(&image.get_bytes(), image.width(), image.height());
 
-        match self.engine.recognize(&image) {
-            Ok(results) => results
-                .into_iter()
-                .map(|v| v.text)
-                .collect::<Vec<_>>()
-                .join(" "),
-            Err(_) => String::new(),
+        match engine.recognize(&image) {
+            Ok(results) if !results.is_empty() => {
+                let confidence = results.iter().map(|v| v.confidence).sum::<f32>() / results.len() as f32;
+                let text = results
+                    .into_iter()
+                    .map(|v| v.text)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                (text, confidence)
+            }
+            _ => (String::new(), 0.0),
         }
     }
 }
@@ -0,0 +1,140 @@
+//! End-of-mission reward summary detection.
+//!
+//! The mission summary shows a screen header followed by a list of collected
+//! rewards, one item per text row. Like the relic reward screen, every region
+//! is derived from relative ratios so detection works across resolutions.
+//!
+//! Only the English client is supported for now.
+
+use regex::Regex;
+
+use crate::debug::detect_debug;
+use crate::{Image, Theme};
+
+/// Header text that is unique to the mission summary screen (lowercase).
+pub const HEADER: &str = "mission complete";
+
+#[derive(Debug, Clone, Default)]
+pub struct MissionRewards {
+    pub rewards: Vec<MissionReward>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MissionReward {
+    pub name: String,
+    pub count: u32,
+}
+
+/// Screen header region (top-left title of the menu).
+fn header(image: Image) -> Image {
+    let w = image.width() as f32;
+    let h = image.height() as f32;
+
+    let x = (w * 0.04).round() as u32;
+    let y = (h * 0.03).round() as u32;
+    let hw = (w * 0.40).round().max(1.0) as u32;
+    let hh = (h * 0.05).round().max(1.0) as u32;
+
+    image.sub_image(x, y, hw, hh)
+}
+
+/// OCR the screen header (top-left title of the menu).
+pub fn header_text(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> String {
+    let text = header(image).get_text(theme, ocr);
+    super::relicreward::normalize_name(&text).to_ascii_lowercase()
+}
+
+/// Max deviation from the theme's text color for a header pixel to count as text.
+const TEXT_MAX_DEVIATION: f32 = 12.0;
+/// Share of the header region a title covers: a few percent for short
+/// titles, never most of it.
+const TEXT_SHARE: std::ops::RangeInclusive<f32> = 0.005..=0.35;
+
+/// Cheap pixel pre-check for [`is_screen`]: whether the header region holds
+/// a title's worth of pixels in the theme's text color. Rejects almost every
+/// in-mission capture without an OCR pass.
+pub fn may_be_screen(image: Image, theme: Theme) -> bool {
+    let header = header(image);
+    let total = header.width() * header.height();
+    let text = header
+        .pixels()
+        .filter(|px| px.deviation(theme.primary) < TEXT_MAX_DEVIATION)
+        .count();
+
+    let share = text as f32 / total.max(1) as f32;
+    detect_debug!("mission header text share={share:.3}");
+    TEXT_SHARE.contains(&share)
+}
+
+/// Whether the capture shows the mission summary screen.
+///
+/// Only captures passing [`may_be_screen`] get their header OCR'd.
+pub fn is_screen(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> bool {
+    may_be_screen(image, theme) && header_text(image, theme, ocr).contains(HEADER)
+}
+
+/// Read the reward list from the mission summary screen.
+///
+/// Rows are OCR'd top to bottom; the scan stops at the first empty row after
+/// at least one reward was found, since the list is contiguous.
+pub fn get_rewards(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> MissionRewards {
+    // Reward rows relative to the image size (derived from a 1920×1080 capture).
+    const LIST_X: f32 = 0.08;
+    const LIST_Y: f32 = 0.22;
+    const LIST_W: f32 = 0.37;
+    const ROW_H: f32 = 0.045;
+    const MAX_ROWS: u32 = 12;
+
+    let w = image.width() as f32;
+    let h = image.height() as f32;
+    if w == 0.0 || h == 0.0 {
+        return MissionRewards::default();
+    }
+
+    let x = (w * LIST_X).round() as u32;
+    let row_w = (w * LIST_W).round().max(1.0) as u32;
+    let row_h = (h * ROW_H).round().max(1.0) as u32;
+    let list_y = (h * LIST_Y).round() as u32;
+
+    let mut rewards = Vec::new();
+    for i in 0..MAX_ROWS {
+        let y = list_y + i * row_h;
+        if y + row_h > image.height() {
+            break;
+        }
+
+        let text = image.sub_image(x, y, row_w, row_h).get_text(theme, ocr);
+        match parse_row(&text) {
+            Some(reward) => rewards.push(reward),
+            None if !rewards.is_empty() => break,
+            None => {}
+        }
+    }
+
+    MissionRewards { rewards }
+}
+
+/// Parse a single reward row such as `"2 X Neurodes"` or `"Ferrite x600"`.
+fn parse_row(text: &str) -> Option<MissionReward> {
+    static LEADING: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+        Regex::new(r"(?i)^(\d+)\s*x\s+(.+)$").expect("regex")
+    });
+    static TRAILING: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
+        Regex::new(r"(?i)^(.+?)\s+x\s*(\d+)$").expect("regex")
+    });
+
+    let text = super::relicreward::normalize_name(text);
+    if !text.chars().any(|c| c.is_alphabetic()) {
+        return None;
+    }
+
+    let (name, count) = if let Some(c) = LEADING.captures(&text) {
+        (c[2].to_string(), c[1].parse().unwrap_or(1))
+    } else if let Some(c) = TRAILING.captures(&text) {
+        (c[1].to_string(), c[2].parse().unwrap_or(1))
+    } else {
+        (text, 1)
+    };
+
+    Some(MissionReward { name, count })
+}
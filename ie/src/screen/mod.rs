@@ -1 +1,4 @@
-pub mod relicreward;
\ No newline at end of file
+//! Screen-specific detectors.
+
+pub mod missionreward;
+pub mod relicreward;
@@ -6,98 +6,413 @@
 //! The logic here is intentionally conservative: if we cannot confidently detect
 //! slots, we return an empty result instead of panicking.
 
+use std::ops::ControlFlow;
+
 use regex::Regex;
 
-use crate::{Image, Theme};
+use crate::debug::detect_debug;
+use crate::{Color, Image, Rect, RelativeRect, Theme};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct Rewards {
     pub timer: u32,
     pub rewards: Vec<RelicReward>,
+    /// Bounding box of all detected slots plus a small margin (empty if none were found).
+    pub reward_area: Rect,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct RelicReward {
     pub name: String,
     pub owned: u32,
+    /// Mean OCR confidence of the name read (0..1).
+    pub confidence: f32,
+    /// Stack size of the reward (e.g. 2 for "2 X Forma Blueprint"), 1 if not
+    /// stacked or not a known stackable reward.
+    pub stack: u32,
+    /// Number of slots showing this reward, including this one (1 = unique).
+    ///
+    /// Set by [`Rewards::mark_duplicates`]; unreadable slots are always 1.
+    pub duplicates: u32,
 }
 
-/// Axis-aligned rectangle in image coordinates.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Rect {
-    x: u32,
-    y: u32,
-    w: u32,
-    h: u32,
+/// Read the reward slots.
+///
+/// `ui_scale` is the in-game HUD scale (1.0 = 100%); slot sizes are measured,
+/// but the minimum sizes used for filtering and OCR crops scale with it.
+///
+/// Only rewards whose name contains one of the `stackable` names get their
+/// stack badge read (see [`RelicReward::stack`]).
+pub fn get_rewards(image: Image, ui_scale: f32, theme: Theme, ocr: &crate::ocr::Ocr, stackable: &[String]) -> Rewards {
+    let slots = detect_reward_slots(image, ui_scale);
+    rewards_in_slots(image, &slots, ui_scale, theme, ocr, stackable)
+}
+
+/// Rewards, selection and slot geometry from a single slot detection pass.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct RelicAnalysis {
+    pub rewards: Rewards,
+    pub selected: Option<usize>,
+    pub rects: Vec<Rect>,
 }
 
-impl Rect {
-    fn right(&self) -> u32 {
-        self.x + self.w
+/// Same as [`get_rewards`] plus [`get_selected`], detecting the slots only once.
+///
+/// Without OCR the rewards are empty; selection and geometry don't need it.
+/// With a `hint` from the previous frame the slots are searched around it
+/// first (see [`detect_reward_slots_hinted`]).
+pub fn analyze(
+    image: Image,
+    ui_scale: f32,
+    theme: Theme,
+    ocr: Option<&crate::ocr::Ocr>,
+    hint: Option<SlotHint>,
+    probe: SelectionProbe,
+    stackable: &[String],
+) -> RelicAnalysis {
+    let rects = match hint {
+        Some(hint) => detect_reward_slots_hinted(image, ui_scale, hint),
+        None => detect_reward_slots(image, ui_scale),
+    };
+    RelicAnalysis {
+        rewards: ocr
+            .map(|ocr| rewards_in_slots(image, &rects, ui_scale, theme, ocr, stackable))
+            .unwrap_or_default(),
+        selected: selected_in_slots(image, &rects, ui_scale, theme, probe),
+        rects,
     }
-    fn bottom(&self) -> u32 {
-        self.y + self.h
+}
+
+fn rewards_in_slots(
+    image: Image,
+    slots: &[Rect],
+    ui_scale: f32,
+    theme: Theme,
+    ocr: &crate::ocr::Ocr,
+    stackable: &[String],
+) -> Rewards {
+    if slots.is_empty() {
+        return Rewards::default();
     }
-    fn center_x(&self) -> u32 {
-        self.x + self.w / 2
+
+    let timer = detect_timer(image, slots, theme, ocr);
+
+    let rewards = slots
+        .iter()
+        .map(|slot| parse_reward(image, *slot, ui_scale, theme, ocr, stackable))
+        .collect();
+
+    let mut rewards = Rewards {
+        timer,
+        rewards,
+        reward_area: reward_area(image, slots),
+    };
+    rewards.mark_duplicates();
+    rewards
+}
+
+impl Rewards {
+    /// Count how many slots show the same reward and store it in
+    /// [`RelicReward::duplicates`].
+    ///
+    /// Names are compared case-insensitively. Call again after replacing names
+    /// (e.g. when voting over several frames).
+    pub fn mark_duplicates(&mut self) {
+        let names = self
+            .rewards
+            .iter()
+            .map(|r| r.name.trim().to_lowercase())
+            .collect::<Vec<_>>();
+        for (reward, name) in self.rewards.iter_mut().zip(&names) {
+            reward.duplicates = match name.is_empty() {
+                true => 1,
+                false => names.iter().filter(|other| *other == name).count() as u32,
+            };
+        }
     }
-    fn center_y(&self) -> u32 {
-        self.y + self.h / 2
+}
+
+/// Bounding box of the slots, grown by a margin and clamped to the image.
+fn reward_area(image: Image, slots: &[Rect]) -> Rect {
+    let Some((first, rest)) = slots.split_first() else {
+        return Rect::default();
+    };
+    let bounds = rest.iter().fold(*first, |acc, slot| acc.union(slot));
+
+    let margin = ((bounds.h as f32) * 0.05).round() as u32;
+    let x = bounds.x.saturating_sub(margin);
+    let y = bounds.y.saturating_sub(margin);
+    let right = (bounds.right() + margin).min(image.width());
+    let bottom = (bounds.bottom() + margin).min(image.height());
+    Rect::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+}
+
+/// Cheap check (no OCR) whether the capture shows the reward screen.
+pub fn is_screen(image: Image, ui_scale: f32) -> bool {
+    reward_slots_present(image, ui_scale)
+}
+
+/// Side of a reward slot at 1080p and 100% HUD scale, the reference for the slot ratios.
+const SLOT_SIDE_1080P: f32 = 235.0;
+
+/// Estimate the in-game HUD scale from the size of the reward slots.
+///
+/// Slots are searched with the smallest supported scale, and the median slot
+/// width is compared to [`SLOT_SIDE_1080P`] at the capture's height. The result
+/// is rounded to 5% steps and clamped to 50%..100%, the range the game offers.
+/// `None` if the capture doesn't show the reward screen.
+pub fn estimate_ui_scale(image: Image) -> Option<f32> {
+    let mut widths = detect_reward_slots(image, 0.5).iter().map(|r| r.w).collect::<Vec<_>>();
+    if widths.is_empty() {
+        return None;
     }
+    widths.sort_unstable();
+    let median = widths[widths.len() / 2] as f32;
 
-    fn iou(&self, other: &Rect) -> f32 {
-        let x1 = self.x.max(other.x);
-        let y1 = self.y.max(other.y);
-        let x2 = self.right().min(other.right());
-        let y2 = self.bottom().min(other.bottom());
+    let expected = SLOT_SIDE_1080P * image.height() as f32 / 1080.0;
+    let scale = (median / expected * 20.0).round() / 20.0;
+    detect_debug!("ui_scale slot_width={median} expected={expected:.1} scale={scale:.2}");
+    Some(scale.clamp(0.5, 1.0))
+}
 
-        if x2 <= x1 || y2 <= y1 {
-            return 0.0;
-        }
+/// Largest [`Color::deviation`] between the reward text and `theme.primary`
+/// for a theme to count as matching (about 15% per channel).
+const THEME_MAX_DEVIATION: f32 = 27.0;
 
-        let inter = (x2 - x1) as f32 * (y2 - y1) as f32;
-        let a1 = (self.w as f32) * (self.h as f32);
-        let a2 = (other.w as f32) * (other.h as f32);
-        inter / (a1 + a2 - inter)
+/// How well a sampled theme matches the text on a reward screen (see [`validate_theme`]).
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct ThemeValidation {
+    /// Dominant color of the reward name text.
+    pub text_color: Color,
+    pub primary_deviation: f32,
+    pub secondary_deviation: f32,
+}
+
+impl ThemeValidation {
+    /// Whether the reward text is close to the primary color, and closer to it
+    /// than to the secondary one (as the theme-guided OCR pass assumes).
+    pub fn matches(&self) -> bool {
+        self.primary_deviation <= THEME_MAX_DEVIATION && self.primary_deviation < self.secondary_deviation
     }
 }
 
-pub fn get_rewards(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> Rewards {
-    let slots = detect_reward_slots(image);
-    if slots.is_empty() {
-        return Rewards {
-            timer: 0,
-            rewards: vec![],
+/// Compare `theme` against the reward name text of a reward screen capture.
+///
+/// The text color is the average of the brightest tenth of the name band
+/// pixels of all slots (glyphs are bright on a dark slot). A large deviation
+/// usually means the theme was sampled on the wrong screen. `None` if the
+/// capture doesn't show the reward screen.
+pub fn validate_theme(image: Image, theme: Theme) -> Option<ThemeValidation> {
+    let slots = detect_reward_slots(image, 0.5);
+
+    let mut hist = [0u32; 256];
+    let mut names = Vec::with_capacity(slots.len());
+    for slot in &slots {
+        let margin = ((slot.w as f32) * 0.05).round().max(1.0) as u32;
+        let name_h = ((slot.h as f32) * 0.30).round().max(1.0) as u32;
+        let Some(name) = image.try_sub_image(
+            slot.x + margin,
+            slot.bottom().saturating_sub(name_h),
+            slot.w.saturating_sub(margin * 2).max(1),
+            name_h,
+        ) else {
+            continue;
         };
+        for (total, n) in hist.iter_mut().zip(name.luma_histogram()) {
+            *total += n;
+        }
+        names.push(name);
     }
 
-    let timer = detect_timer(image, &slots, theme, ocr);
+    // Luma above which the brightest tenth of the pixels lies.
+    let total = hist.iter().sum::<u32>();
+    let mut above = 0;
+    let cutoff = (0..=255u8).rev().find(|&l| {
+        above += hist[l as usize];
+        above * 10 >= total
+    })?;
 
-    let rewards = slots
-        .iter()
-        .map(|slot| parse_reward(image, *slot, theme, ocr))
-        .collect();
+    let (mut r, mut g, mut b, mut count) = (0u64, 0u64, 0u64, 0u64);
+    for c in names.iter().flat_map(|name| name.pixels()).filter(|c| c.luma() >= cutoff) {
+        r += c.r as u64;
+        g += c.g as u64;
+        b += c.b as u64;
+        count += 1;
+    }
+    if count == 0 {
+        return None;
+    }
+    let text_color = Color::new((r / count) as u8, (g / count) as u8, (b / count) as u8);
+
+    let validation = ThemeValidation {
+        text_color,
+        primary_deviation: text_color.deviation(theme.primary),
+        secondary_deviation: text_color.deviation(theme.secondary),
+    };
+    detect_debug!("theme validation {validation:?}");
+    Some(validation)
+}
+
+/// Reward rarity, shown as a bronze/silver/gold bead on the slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+/// Tolerances for [`classify_rarity`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RarityTolerances {
+    /// Pixels darker than this HSV value (0..1) are background and ignored.
+    pub min_value: f32,
+    /// Pixels with at least this saturation (0..1) count towards the hue.
+    pub min_saturation: f32,
+    /// Silver if fewer than this fraction of the bright pixels are saturated.
+    pub max_silver_saturated: f32,
+    /// Hue range (degrees) of the bronze bead.
+    pub bronze_hue: (f32, f32),
+    /// Hue range (degrees) of the gold bead.
+    pub gold_hue: (f32, f32),
+}
+
+impl Default for RarityTolerances {
+    fn default() -> Self {
+        Self {
+            min_value: 0.35,
+            min_saturation: 0.3,
+            max_silver_saturated: 0.3,
+            bronze_hue: (10.0, 38.0),
+            gold_hue: (38.0, 65.0),
+        }
+    }
+}
+
+/// Rarity bead region within a slot, relative to the 235px reference slot.
+const RARITY_BEAD: (f32, f32, f32, f32) = (8.0, 8.0, 20.0, 20.0);
+
+/// Classify the rarity bead of a reward slot by its dominant hue.
+///
+/// The theme tints the slot but barely shifts the bead's hue, so this is more
+/// stable than comparing against RGB templates: silver is the unsaturated bead,
+/// bronze and gold are told apart by where the most common saturated hue falls.
+/// `None` if the bead region is empty, dark or its hue fits neither range.
+pub fn classify_rarity(image: Image, slot: Rect, tolerances: RarityTolerances) -> Option<Rarity> {
+    let scale = slot.w as f32 / SLOT_SIDE_1080P;
+    let (bx, by, bw, bh) = RARITY_BEAD;
+    let bead = image.try_sub_image(
+        slot.x + (bx * scale).round() as u32,
+        slot.y + (by * scale).round() as u32,
+        ((bw * scale).round() as u32).max(1),
+        ((bh * scale).round() as u32).max(1),
+    )?;
+
+    // 10 degree hue buckets over the bright, saturated pixels.
+    let mut hues = [0u32; 36];
+    let mut bright = 0u32;
+    let mut saturated = 0u32;
+    for (hue, sat, value) in bead.pixels().map(|c| c.to_hsv()) {
+        if value < tolerances.min_value {
+            continue;
+        }
+        bright += 1;
+        if sat >= tolerances.min_saturation {
+            saturated += 1;
+            hues[(hue / 10.0) as usize % 36] += 1;
+        }
+    }
+
+    if bright == 0 {
+        return None;
+    }
+    if (saturated as f32 / bright as f32) < tolerances.max_silver_saturated {
+        return Some(Rarity::Uncommon);
+    }
 
-    Rewards { timer, rewards }
+    let (bucket, _) = hues.iter().enumerate().max_by_key(|(i, n)| (**n, std::cmp::Reverse(*i)))?;
+    let hue = bucket as f32 * 10.0 + 5.0;
+    let in_range = |(lo, hi): (f32, f32)| (lo..hi).contains(&hue);
+    detect_debug!("rarity bright={bright} saturated={saturated} hue={hue}");
+    if in_range(tolerances.bronze_hue) {
+        Some(Rarity::Common)
+    } else if in_range(tolerances.gold_hue) {
+        Some(Rarity::Rare)
+    } else {
+        None
+    }
+}
+
+/// Max deviation from the theme color for the highlight probe at 100% HUD scale.
+const SELECTED_MAX_DEVIATION: f32 = 12.0;
+
+/// Slot corner a [`SelectionProbe`] is measured from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Corner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
 }
 
-pub fn get_selected(image: Image, theme: Theme) -> Option<usize> {
-    let slots = detect_reward_slots(image);
+/// Where [`get_selected`] looks for the selection highlight within a slot.
+///
+/// Size and insets are in pixels of the 235px reference slot and scale with
+/// the detected slot. The default is the small square near the top-right
+/// corner the current UI draws.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SelectionProbe {
+    pub corner: Corner,
+    /// Side of the probed square.
+    pub size: f32,
+    /// Distance from the corner's vertical slot edge.
+    pub inset_x: f32,
+    /// Distance from the corner's horizontal slot edge.
+    pub inset_y: f32,
+}
+
+impl Default for SelectionProbe {
+    fn default() -> Self {
+        Self {
+            corner: Corner::TopRight,
+            size: 12.0,
+            inset_x: 5.0,
+            inset_y: 4.0,
+        }
+    }
+}
+
+/// Detect which reward slot is highlighted as selected.
+///
+/// The probe is sized from the slot, but at smaller HUD scales it covers fewer
+/// pixels and picks up more anti-aliased edge color, so its padding floor and
+/// the deviation threshold are adjusted by `ui_scale`.
+pub fn get_selected(image: Image, ui_scale: f32, theme: Theme, probe: SelectionProbe) -> Option<usize> {
+    selected_in_slots(image, &detect_reward_slots(image, ui_scale), ui_scale, theme, probe)
+}
+
+fn selected_in_slots(image: Image, slots: &[Rect], ui_scale: f32, theme: Theme, probe: SelectionProbe) -> Option<usize> {
     if slots.is_empty() {
         return None;
     }
 
-    // Selected highlight is a small square near the top-right of a slot.
+    // Selected highlight is a small square near a slot corner (top-right by default).
     // We compare it to the sampled theme secondary color.
     let mut best: Option<(usize, f32)> = None;
 
     for (i, slot) in slots.iter().enumerate() {
-        let size = ((slot.w as f32) * 12.0 / 235.0).round().max(6.0) as u32;
-        let pad_r = ((slot.w as f32) * 5.0 / 235.0).round().max(1.0) as u32;
-        let pad_t = ((slot.h as f32) * 4.0 / 235.0).round().max(1.0) as u32;
+        let size = ((slot.w as f32) * probe.size / 235.0).round().max(6.0 * ui_scale).max(1.0) as u32;
+        let pad_x = ((slot.w as f32) * probe.inset_x / 235.0).round().max(2.0 * ui_scale).max(1.0) as u32;
+        let pad_y = ((slot.h as f32) * probe.inset_y / 235.0).round().max(2.0 * ui_scale).max(1.0) as u32;
 
-        let x = slot.x.saturating_add(slot.w.saturating_sub(size + pad_r));
-        let y = slot.y.saturating_add(pad_t);
+        let x = match probe.corner {
+            Corner::TopLeft | Corner::BottomLeft => slot.x.saturating_add(pad_x),
+            Corner::TopRight | Corner::BottomRight => slot.x.saturating_add(slot.w.saturating_sub(size + pad_x)),
+        };
+        let y = match probe.corner {
+            Corner::TopLeft | Corner::TopRight => slot.y.saturating_add(pad_y),
+            Corner::BottomLeft | Corner::BottomRight => slot.y.saturating_add(slot.h.saturating_sub(size + pad_y)),
+        };
 
         let sw = size.min(image.width().saturating_sub(x));
         let sh = size.min(image.height().saturating_sub(y));
@@ -107,6 +422,7 @@ pub fn get_selected(image: Image, theme: Theme) -> Option<usize> {
 
         let avg = image.sub_image(x, y, sw, sh).average_color();
         let dev = avg.deviation(theme.secondary);
+        detect_debug!("selection slot={i} probe=({x}, {y}) {sw}x{sh} deviation={dev:.1}");
 
         match best {
             None => best = Some((i, dev)),
@@ -117,50 +433,208 @@ pub fn get_selected(image: Image, theme: Theme) -> Option<usize> {
 
     // Threshold is intentionally loose; false positives are filtered by comparing
     // the winner to the runner-up if necessary.
-    best.and_then(|(idx, dev)| if dev < 12.0 { Some(idx) } else { None })
+    let max_dev = SELECTED_MAX_DEVIATION * (2.0 - ui_scale.clamp(0.5, 1.0));
+    detect_debug!("selection best={best:?} max_deviation={max_dev:.1}");
+    best.and_then(|(idx, dev)| if dev < max_dev { Some(idx) } else { None })
 }
 
-fn parse_reward(image: Image, slot: Rect, theme: Theme, ocr: &crate::ocr::Ocr) -> RelicReward {
-    let slot_img = image.sub_image(slot.x, slot.y, slot.w, slot.h);
+/// Below this mean OCR confidence a name read is suspect...
+const MIN_NAME_CONFIDENCE: f32 = 0.6;
+/// ...and below this score (roughly three alphanumerics) it is too short to be
+/// an item name. Only reads failing both are dropped, so a short but confident
+/// read ("Forma") or a long but shaky one still reaches the fuzzy matcher.
+const MIN_NAME_SCORE: i64 = 12;
+
+/// Top part of a slot searched for the owned count.
+const OWNED_SEARCH_FRACTION: f32 = 0.35;
+
+fn parse_reward(
+    image: Image,
+    slot: Rect,
+    ui_scale: f32,
+    theme: Theme,
+    ocr: &crate::ocr::Ocr,
+    stackable: &[String],
+) -> RelicReward {
+    let unreadable = RelicReward {
+        name: String::new(),
+        owned: 0,
+        confidence: 0.0,
+        stack: 1,
+        duplicates: 1,
+    };
+
+    // Crops that don't fit are detection bugs; report the slot as unreadable
+    // rather than OCR a silently shrunken region.
+    let Some(slot_img) = image.try_sub_image_rect(slot) else {
+        detect_debug!("reward slot={slot:?} outside the image");
+        return unreadable;
+    };
 
     let margin = ((slot.w as f32) * 0.05).round().max(1.0) as u32;
 
     // Name is typically at the bottom of the slot.
-    let name_h = ((slot.h as f32) * 0.30).round().max(12.0) as u32;
+    let name_h = ((slot.h as f32) * 0.30).round().max(12.0 * ui_scale).max(1.0) as u32;
     let name_y = slot.h.saturating_sub(name_h);
     let name_w = slot.w.saturating_sub(margin * 2).max(1);
-    let name_img = slot_img.sub_image(margin, name_y, name_w, name_h);
+    let Some(name_img) = slot_img.try_sub_image(margin, name_y, name_w, name_h) else {
+        detect_debug!("reward slot={slot:?} name crop doesn't fit");
+        return unreadable;
+    };
+
+    let read = name_img.read_text(theme, ocr);
+    let name = if read.confidence < MIN_NAME_CONFIDENCE && read.score < MIN_NAME_SCORE {
+        // Garbage would be fuzzy-matched onto some real item downstream;
+        // an empty name shows up as unreadable instead.
+        String::new()
+    } else {
+        normalize_name(&read.text)
+    };
+
+    // Owned/crafted count is usually in the top band of the slot, but moves
+    // down at some HUD scales. Read the top band and, without a labeled hit,
+    // the lowest band of the top part (never into the name); an unlabeled
+    // number is only trusted from the top band.
+    let owned_h = ((slot.h as f32) * 0.14).round().max(10.0 * ui_scale).max(1.0) as u32;
+    let search_h = (((slot.h as f32) * OWNED_SEARCH_FRACTION).round() as u32).min(name_y).max(owned_h);
+    let low_y = search_h - owned_h;
 
-    let mut name = name_img.get_text(theme, ocr);
-    name = normalize_name(&name);
+    let mut owned = None;
+    let mut top_text = None;
+    for y in [Some(0), (low_y > 0).then_some(low_y)].into_iter().flatten() {
+        let Some(band) = slot_img.try_sub_image(margin, y, name_w, owned_h) else {
+            break;
+        };
+        let text = band.get_text(theme, ocr);
+        if let Some(count) = parse_owned_count_labeled(&text) {
+            owned = Some(count);
+            break;
+        }
+        top_text.get_or_insert(text);
+    }
+    let owned = owned
+        .or_else(|| top_text.as_deref().and_then(parse_owned_count))
+        .unwrap_or(0);
 
-    // Owned/crafted count is often near the top of the slot.
-    let owned_h = ((slot.h as f32) * 0.14).round().max(10.0) as u32;
-    let owned_img = slot_img.sub_image(margin, 0, name_w, owned_h);
-    let owned_text = owned_img.get_text(theme, ocr);
+    // Stacked rewards show an "x2" badge at the lower right of the icon, between
+    // the owned band and the name. The name OCR often drops its "2 X" prefix, so
+    // the badge is read on its own and the name prefix is only a fallback.
+    // Only known stackable rewards have a badge; elsewhere an "x"-digit read in
+    // the icon is noise and would multiply the reward's value.
+    let stack = match is_stackable(&name, stackable) {
+        true => {
+            let badge_y = owned_h;
+            let badge_h = name_y.saturating_sub(badge_y).max(1);
+            slot_img
+                .try_sub_image(slot.w / 2, badge_y, slot.w / 2, badge_h)
+                .and_then(|badge_img| parse_stack_badge(&badge_img.get_text(theme, ocr)))
+                .or_else(|| parse_stack_prefix(&name))
+                .unwrap_or(1)
+        }
+        false => 1,
+    };
 
-    let owned = parse_owned_count(&owned_text).unwrap_or(0);
+    RelicReward {
+        name,
+        owned,
+        confidence: read.confidence,
+        stack,
+        duplicates: 1,
+    }
+}
+
+/// Whether the read name contains one of the `stackable` reward names (case-insensitive).
+fn is_stackable(name: &str, stackable: &[String]) -> bool {
+    let name = name.to_lowercase();
+    stackable.iter().any(|s| !s.is_empty() && name.contains(&s.to_lowercase()))
+}
+
+/// Parse a stack badge like `"x2"`, `"X 3"` or `"2x"` (`×` accepted too).
+fn parse_stack_badge(text: &str) -> Option<u32> {
+    static RE: std::sync::LazyLock<Regex> =
+        std::sync::LazyLock::new(|| Regex::new(r"(?i)(?:[x×]\s*(\d{1,2})\b|\b(\d{1,2})\s*[x×])").expect("regex"));
+
+    let caps = RE.captures(text)?;
+    let count = caps.get(1).or_else(|| caps.get(2))?.as_str().parse::<u32>().ok()?;
+    (count >= 2).then_some(count)
+}
 
-    RelicReward { name, owned }
+/// Parse the `"2 X "` prefix of a stacked reward name.
+fn parse_stack_prefix(name: &str) -> Option<u32> {
+    let (count, _) = name.split_once([' ', 'X', 'x', '×'])?;
+    let count = count.trim().parse::<u32>().ok()?;
+    (count >= 2).then_some(count)
 }
 
-fn normalize_name(raw: &str) -> String {
-    raw.replace('\n', " ")
+/// Clean up an OCR'd name before matching.
+///
+/// Kept conservative so legitimate punctuation inside names ("Ack & Brunt")
+/// survives: only whitespace, the outer edges, a known glyph confusion and
+/// repeated punctuation are touched.
+pub(crate) fn normalize_name(raw: &str) -> String {
+    let joined = raw
         .split_whitespace()
+        .map(|word| {
+            // A vertical bar at the start of a word is almost always a misread "I",
+            // elsewhere it's a lowercase "l".
+            word.char_indices()
+                .map(|(i, c)| match c {
+                    '|' if i == 0 => 'I',
+                    '|' => 'l',
+                    c => c,
+                })
+                .collect::<String>()
+        })
         .collect::<Vec<_>>()
-        .join(" ")
-        .trim()
-        .to_string()
+        .join(" ");
+
+    // Collapse runs of the same punctuation ("Prime.." -> "Prime.").
+    let mut out = String::with_capacity(joined.len());
+    let mut prev = None;
+    for c in joined.chars() {
+        if c.is_ascii_punctuation() && prev == Some(c) {
+            continue;
+        }
+        out.push(c);
+        prev = Some(c);
+    }
+
+    out.trim_matches(|c: char| !c.is_alphanumeric()).to_string()
 }
 
+/// Label words in front of the owned count, for every supported client language.
+const OWNED_LABELS: &str = "OWNED|CRAFTED|BESITZ|HERGESTELLT";
+
+/// Parse the owned count from the slot header (e.g. `"OWNED x 12"`, `"CRAFTED: 3"`, `"OWNED 1,024"`).
+///
+/// Thousands separators (`,` `.` or a no-break space) are accepted. If no
+/// label word is recognized, the first standalone integer is used instead.
 fn parse_owned_count(text: &str) -> Option<u32> {
+    static RE_FALLBACK: std::sync::LazyLock<Regex> =
+        std::sync::LazyLock::new(|| Regex::new(&format!(r"\b{OWNED_NUMBER}\b")).expect("regex"));
+
+    parse_owned_count_labeled(text).or_else(|| RE_FALLBACK.captures(text).and_then(|c| owned_number(c.get(1)?)))
+}
+
+/// Either a separator-grouped number or a plain run of digits.
+const OWNED_NUMBER: &str = r"(\d{1,3}(?:[,.\u{A0}\u{202F}]\d{3})+|\d+)";
+
+/// Same as [`parse_owned_count`], but only accepts a number behind a label word.
+fn parse_owned_count_labeled(text: &str) -> Option<u32> {
     static RE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| {
-        Regex::new(r"(?i)\b(?:OWNED|CRAFTED)\s*x?\s*(\d+)").expect("regex")
+        Regex::new(&format!(r"(?i)\b(?:{OWNED_LABELS})\s*:?\s*x?\s*{OWNED_NUMBER}\b")).expect("regex")
     });
 
-    RE.captures(text)
-        .and_then(|c| c.get(1))
-        .and_then(|m| m.as_str().parse::<u32>().ok())
+    RE.captures(text).and_then(|c| owned_number(c.get(1)?))
+}
+
+fn owned_number(m: regex::Match) -> Option<u32> {
+    m.as_str()
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse::<u32>()
+        .ok()
 }
 
 fn detect_timer(image: Image, slots: &[Rect], theme: Theme, ocr: &crate::ocr::Ocr) -> u32 {
@@ -186,30 +660,178 @@ fn detect_timer(image: Image, slots: &[Rect], theme: Theme, ocr: &crate::ocr::Oc
 
     // Extract the first number we can find.
     let digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
+    detect_debug!("timer region=({x}, {y}) {w}x{h} raw={text:?} digits={digits:?}");
     digits.parse::<u32>().unwrap_or(0)
 }
 
-fn detect_reward_slots(image: Image) -> Vec<Rect> {
-    use imageproc::contrast::{equalize_histogram, otsu_level, threshold, ThresholdType};
-    use imageproc::contours::{find_contours, BorderType};
+/// Whether there is at least one reward slot, without collecting or grouping them.
+///
+/// Same result as `!detect_reward_slots(..).is_empty()`, but stops at the
+/// first slot-shaped contour. Meant for polling; a solo run only shows a
+/// single slot, so one is enough.
+pub fn reward_slots_present(image: Image, ui_scale: f32) -> bool {
+    let Some(search) = SlotSearch::full(image, ui_scale) else {
+        return false;
+    };
+    slot_candidates(image, search, |_| ControlFlow::Break(())).is_break()
+}
+
+/// Where a previous frame found the slots, to narrow the next search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotHint {
+    /// Bounding box of the slots.
+    pub area: Rect,
+    /// Mean slot side in pixels.
+    pub slot_side: u32,
+}
+
+impl SlotHint {
+    /// Hint from detected slots, `None` if there are none.
+    pub fn from_slots(slots: &[Rect]) -> Option<Self> {
+        let (first, rest) = slots.split_first()?;
+        let area = rest.iter().fold(*first, |acc, slot| acc.union(slot));
+        let slot_side = (slots.iter().map(|r| (r.w + r.h) as u64).sum::<u64>() / (2 * slots.len() as u64)) as u32;
+        Some(Self { area, slot_side })
+    }
+}
+
+/// Search region and accepted slot sizes for [`slot_candidates`].
+#[derive(Debug, Clone, Copy)]
+struct SlotSearch {
+    roi: Rect,
+    min_side: u32,
+    max_side: u32,
+}
+
+impl SlotSearch {
+    /// The broad default search, `None` for an empty image.
+    fn full(image: Image, ui_scale: f32) -> Option<Self> {
+        let h = image.height();
+        if image.width() == 0 || h == 0 {
+            return None;
+        }
+
+        // Restrict to a broad ROI around the expected rewards area (relative coordinates).
+        // This is *not* a fixed-pixel approach: it scales with resolution.
+        const ROI: RelativeRect = RelativeRect::new(0.15, 0.18, 0.70, 0.57);
+
+        // Slots shrink with the HUD scale, so only the lower bound follows it.
+        Some(Self {
+            roi: ROI.to_pixels(image),
+            min_side: (h as f32 * 0.12 * ui_scale.clamp(0.25, 1.0)) as u32,
+            max_side: (h as f32 * 0.40) as u32,
+        })
+    }
+
+    /// Around the hinted area (one slot of margin), for slots within 20% of the hinted size.
+    fn hinted(image: Image, hint: SlotHint) -> Option<Self> {
+        let margin = hint.slot_side;
+        let x = hint.area.x.saturating_sub(margin);
+        let y = hint.area.y.saturating_sub(margin);
+        let right = (hint.area.right() + margin).min(image.width());
+        let bottom = (hint.area.bottom() + margin).min(image.height());
+        if right <= x || bottom <= y || hint.slot_side == 0 {
+            return None;
+        }
 
-    let w = image.width();
+        Some(Self {
+            roi: Rect::new(x, y, right - x, bottom - y),
+            min_side: (hint.slot_side as f32 * 0.8) as u32,
+            max_side: (hint.slot_side as f32 * 1.2).ceil() as u32,
+        })
+    }
+}
+
+/// Find the reward slot rectangles (left to right) without running OCR.
+pub fn detect_reward_slots(image: Image, ui_scale: f32) -> Vec<Rect> {
+    match SlotSearch::full(image, ui_scale) {
+        Some(search) => find_slots(image, search),
+        None => vec![],
+    }
+}
+
+/// [`detect_reward_slots`] that searches around the slots of a previous frame
+/// first, falling back to the full search if nothing is found there.
+///
+/// The reward screen doesn't move while it is shown, so the narrow search is
+/// cheaper and can't pick up slot-sized shapes elsewhere on screen.
+pub fn detect_reward_slots_hinted(image: Image, ui_scale: f32, hint: SlotHint) -> Vec<Rect> {
+    let slots = SlotSearch::hinted(image, hint)
+        .map(|search| find_slots(image, search))
+        .unwrap_or_default();
+    if !slots.is_empty() {
+        return slots;
+    }
+
+    detect_debug!("slots hint={hint:?} found nothing, falling back to the full search");
+    detect_reward_slots(image, ui_scale)
+}
+
+/// Slot candidates of `search`, reduced to the fullest row.
+fn find_slots(image: Image, search: SlotSearch) -> Vec<Rect> {
     let h = image.height();
-    if w == 0 || h == 0 {
-        return vec![];
+
+    let mut rects = Vec::new();
+    let _ = slot_candidates(image, search, |r| {
+        rects.push(r);
+        ControlFlow::<()>::Continue(())
+    });
+
+    if rects.is_empty() {
+        return rects;
+    }
+
+    // Group candidates by approximate row (y coordinate) and keep the row with the most slots.
+    let tol = (h as f32 * 0.06).round().max(1.0) as u32;
+
+    // BTreeMap so ties between equally full rows always resolve the same way
+    // (`max_by_key` keeps the last, i.e. lowest on screen), unlike HashMap order.
+    use std::collections::BTreeMap;
+    let mut buckets: BTreeMap<u32, Vec<Rect>> = BTreeMap::new();
+    for r in rects {
+        let key = r.center_y() / tol;
+        buckets.entry(key).or_default().push(r);
+    }
+
+    detect_debug!(
+        "slots rows={:?}",
+        buckets.iter().map(|(row, v)| (row * tol, v.len())).collect::<Vec<_>>()
+    );
+    let mut best_row = buckets
+        .into_values()
+        .max_by_key(|v| v.len())
+        .unwrap_or_default();
+
+    // Sort left-to-right and deduplicate heavy overlaps.
+    best_row.sort_by_key(|r| r.x);
+    let mut dedup: Vec<Rect> = Vec::new();
+    for r in best_row {
+        if let Some(prev) = dedup.last_mut() {
+            if prev.iou(&r) > 0.5 {
+                // Keep the larger rect.
+                if r.area() > prev.area() {
+                    *prev = r;
+                }
+                continue;
+            }
+        }
+        dedup.push(r);
     }
 
-    // Restrict to a broad ROI around the expected rewards area (relative coordinates).
-    // This is *not* a fixed-pixel approach: it scales with resolution.
-    let roi_x1 = (w as f32 * 0.15).round() as u32;
-    let roi_x2 = (w as f32 * 0.85).round() as u32;
-    let roi_y1 = (h as f32 * 0.18).round() as u32;
-    let roi_y2 = (h as f32 * 0.75).round() as u32;
+    detect_debug!("slots count={} chosen={:?}", dedup.len(), dedup);
+    dedup
+}
+
+/// Pass every slot-shaped contour in the search region to `f` (in contour
+/// order, absolute coordinates), until it breaks.
+fn slot_candidates<B>(image: Image, search: SlotSearch, mut f: impl FnMut(Rect) -> ControlFlow<B>) -> ControlFlow<B> {
+    use imageproc::contrast::{equalize_histogram, otsu_level, threshold, ThresholdType};
+    use imageproc::contours::{find_contours, BorderType};
 
-    let roi_w = roi_x2.saturating_sub(roi_x1).max(1);
-    let roi_h = roi_y2.saturating_sub(roi_y1).max(1);
+    let SlotSearch { roi: roi_rect, min_side, max_side } = search;
+    let (roi_x1, roi_y1) = (roi_rect.x, roi_rect.y);
 
-    let roi = image.sub_image(roi_x1, roi_y1, roi_w, roi_h).to_owned_image();
+    let roi = image.sub_image_rect(roi_rect).to_owned_image();
     let gray = equalize_histogram(&roi.to_gray_image());
     let level = otsu_level(&gray);
     let mut bin = threshold(&gray, level, ThresholdType::Binary);
@@ -220,10 +842,6 @@ fn detect_reward_slots(image: Image) -> Vec<Rect> {
     // Find contours on the binarized ROI.
     let contours = find_contours::<i32>(&bin);
 
-    let min_side = (h as f32 * 0.12) as u32;
-    let max_side = (h as f32 * 0.40) as u32;
-
-    let mut rects = Vec::new();
     for c in contours {
         if c.border_type != BorderType::Outer {
             continue;
@@ -253,59 +871,22 @@ fn detect_reward_slots(image: Image) -> Vec<Rect> {
 
         // Filter by approximate size and aspect ratio (reward slots are close to square).
         if rw < min_side || rh < min_side || rw > max_side || rh > max_side {
+            // Tiny contours are glyph/noise fragments; only log plausible ones.
+            if rw.max(rh) >= min_side / 2 {
+                detect_debug!("slots reject=size rect=({min_x}, {min_y}) {rw}x{rh} allowed={min_side}..={max_side}");
+            }
             continue;
         }
         let aspect = rw as f32 / rh as f32;
         if !(0.80..=1.25).contains(&aspect) {
+            detect_debug!("slots reject=aspect rect=({min_x}, {min_y}) {rw}x{rh} aspect={aspect:.2}");
             continue;
         }
 
-        rects.push(Rect {
-            x: roi_x1 + (min_x as u32),
-            y: roi_y1 + (min_y as u32),
-            w: rw,
-            h: rh,
-        });
-    }
-
-    if rects.is_empty() {
-        return rects;
-    }
-
-    // Group candidates by approximate row (y coordinate) and keep the row with the most slots.
-    let tol = (h as f32 * 0.06).round().max(1.0) as u32;
-
-    use std::collections::HashMap;
-    let mut buckets: HashMap<u32, Vec<Rect>> = HashMap::new();
-    for r in rects {
-        let key = r.center_y() / tol;
-        buckets.entry(key).or_default().push(r);
-    }
-
-    let mut best_row = buckets
-        .into_values()
-        .max_by_key(|v| v.len())
-        .unwrap_or_default();
-
-    // Sort left-to-right and deduplicate heavy overlaps.
-    best_row.sort_by_key(|r| r.x);
-    let mut dedup: Vec<Rect> = Vec::new();
-    for r in best_row {
-        if let Some(prev) = dedup.last_mut() {
-            if prev.iou(&r) > 0.5 {
-                // Keep the larger rect.
-                let prev_area = (prev.w as u64) * (prev.h as u64);
-                let r_area = (r.w as u64) * (r.h as u64);
-                if r_area > prev_area {
-                    *prev = r;
-                }
-                continue;
-            }
-        }
-        dedup.push(r);
+        f(Rect::new(roi_x1 + (min_x as u32), roi_y1 + (min_y as u32), rw, rh))?;
     }
 
-    dedup
+    ControlFlow::Continue(())
 }
 
 fn normalize_binary(bin: &mut image::GrayImage) {
@@ -325,3 +906,125 @@ fn normalize_binary(bin: &mut image::GrayImage) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OwnedImage;
+
+    #[test]
+    fn owned_count_english_labels() {
+        assert_eq!(parse_owned_count("OWNED x 12"), Some(12));
+        assert_eq!(parse_owned_count("CRAFTED: 3"), Some(3));
+        assert_eq!(parse_owned_count("owned 7"), Some(7));
+    }
+
+    #[test]
+    fn owned_count_german_labels() {
+        assert_eq!(parse_owned_count("BESITZ: 4"), Some(4));
+        assert_eq!(parse_owned_count("HERGESTELLT 5"), Some(5));
+    }
+
+    #[test]
+    fn owned_count_grouped_numbers() {
+        assert_eq!(parse_owned_count("OWNED 1,024"), Some(1024));
+        assert_eq!(parse_owned_count("BESITZ 1.024"), Some(1024));
+        assert_eq!(parse_owned_count("OWNED 1\u{A0}024"), Some(1024));
+        assert_eq!(parse_owned_count("OWNED 12,345,678"), Some(12_345_678));
+    }
+
+    #[test]
+    fn owned_count_without_label() {
+        assert_eq!(parse_owned_count_labeled("12"), None);
+        assert_eq!(parse_owned_count("12"), Some(12));
+        assert_eq!(parse_owned_count("1,024"), Some(1024));
+        assert_eq!(parse_owned_count("OWNED"), None);
+        assert_eq!(parse_owned_count(""), None);
+    }
+
+    #[test]
+    fn owned_count_labeled_prefers_the_label() {
+        assert_eq!(parse_owned_count_labeled("3 OWNED 12"), Some(12));
+        assert_eq!(parse_owned_count("3 OWNED 12"), Some(12));
+    }
+
+    #[test]
+    fn normalize_name_fixes_bars_and_punctuation() {
+        assert_eq!(normalize_name("|ron  Skana"), "Iron Skana");
+        assert_eq!(normalize_name("Nova Prime Systems"), "Nova Prime Systems");
+        assert_eq!(normalize_name("Rhino Prime.. Blueprint"), "Rhino Prime. Blueprint");
+        assert_eq!(normalize_name("  -Forma Blueprint-  "), "Forma Blueprint");
+        assert_eq!(normalize_name("Ack & Brunt"), "Ack & Brunt");
+        assert_eq!(normalize_name("Bo|tor Prime"), "Boltor Prime");
+    }
+
+    #[test]
+    fn stack_badge() {
+        assert_eq!(parse_stack_badge("x2"), Some(2));
+        assert_eq!(parse_stack_badge("X 3"), Some(3));
+        assert_eq!(parse_stack_badge("2x"), Some(2));
+        assert_eq!(parse_stack_badge("×4"), Some(4));
+        assert_eq!(parse_stack_badge("x1"), None);
+        assert_eq!(parse_stack_badge("Forma"), None);
+    }
+
+    #[test]
+    fn stack_prefix() {
+        assert_eq!(parse_stack_prefix("2 X Forma Blueprint"), Some(2));
+        assert_eq!(parse_stack_prefix("3X Forma Blueprint"), Some(3));
+        assert_eq!(parse_stack_prefix("Forma Blueprint"), None);
+        assert_eq!(parse_stack_prefix("1 X Forma Blueprint"), None);
+    }
+
+    const THEME: Theme = Theme {
+        primary: Color::new(190, 169, 102),
+        secondary: Color::new(240, 200, 120),
+    };
+
+    /// A 1080p capture with `count` square slots of side `side` in a row, the
+    /// top-right corner of slot `selected` (if any) lit in the theme's secondary color.
+    fn slot_row(count: u32, side: u32, selected: Option<usize>) -> (OwnedImage, Vec<Rect>) {
+        let (width, height) = (1920, 1080);
+        let mut img = OwnedImage::from_rgba(width, &[16, 22, 31, 255].repeat(width * height));
+
+        let gap = side / 10;
+        let left = (width as u32 - count * side - (count - 1) * gap) / 2;
+        let slots = (0..count)
+            .map(|i| Rect::new(left + i * (side + gap), 300, side, side))
+            .collect::<Vec<_>>();
+
+        for (i, slot) in slots.iter().enumerate() {
+            img.fill_rect(*slot, Color::new(40, 44, 52), 1.0);
+            if selected == Some(i) {
+                // larger than the probe, so it is lit whatever the exact insets
+                let corner = side / 8;
+                img.fill_rect(Rect::new(slot.right() - corner, slot.y, corner, corner), THEME.secondary, 1.0);
+            }
+        }
+
+        (img, slots)
+    }
+
+    #[test]
+    fn selected_slot() {
+        for selected in 0..4 {
+            let (img, slots) = slot_row(4, 235, Some(selected));
+            let found = selected_in_slots(img.as_image(), &slots, 1.0, THEME, SelectionProbe::default());
+            assert_eq!(found, Some(selected));
+        }
+    }
+
+    #[test]
+    fn selected_slot_half_ui_scale() {
+        let (img, slots) = slot_row(4, 118, Some(2));
+        let found = selected_in_slots(img.as_image(), &slots, 0.5, THEME, SelectionProbe::default());
+        assert_eq!(found, Some(2));
+    }
+
+    #[test]
+    fn no_selected_slot() {
+        let (img, slots) = slot_row(4, 235, None);
+        assert_eq!(selected_in_slots(img.as_image(), &slots, 1.0, THEME, SelectionProbe::default()), None);
+        assert_eq!(selected_in_slots(img.as_image(), &[], 1.0, THEME, SelectionProbe::default()), None);
+    }
+}
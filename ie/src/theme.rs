@@ -1,4 +1,4 @@
-use crate::{Color, Image};
+use crate::{Color, Image, RelativeRect};
 
 /// UI theme colors sampled from the game options screen.
 ///
@@ -10,6 +10,12 @@ pub struct Theme {
     pub secondary: Color,
 }
 
+// Derived from the previous hard-coded 1920×1080 coordinates.
+const BAR: RelativeRect = RelativeRect::from_1080p(110.0, 87.0, 20.0, 1.0);
+const MOUSE: RelativeRect = RelativeRect::from_1080p(146.0, 181.0, 14.0, 8.0);
+/// [`MOUSE`] moved down past the hovered entry.
+const MOUSE_NEIGHBOUR: RelativeRect = RelativeRect::from_1080p(146.0, 241.0, 14.0, 8.0);
+
 impl Theme {
     pub const WHITE: Self = Self {
         primary: Color::WHITE,
@@ -18,31 +24,36 @@ impl Theme {
 
     /// Sample theme colors from a Warframe options screen capture.
     ///
+    /// `primary` comes from the tab bar, `secondary` from the hover highlight of
+    /// the first entry in the options list. The mouse cursor **must** hover that
+    /// entry while capturing, otherwise `secondary` is just the menu background;
+    /// use [`Theme::from_options_checked`] to reject such captures.
+    ///
     /// The original implementation assumed a 1920×1080 capture. To support
     /// arbitrary resolutions and UI scaling, we compute sampling rectangles
     /// as *relative* coordinates.
     pub fn from_options(image: Image) -> Self {
-        let w = image.width().max(1);
-        let h = image.height().max(1);
-
-        // Ratios were derived from the previous hard-coded 1920×1080 coordinates:
-        // primary: (110,87) size (20×1)
-        // secondary: (146,181) size (14×8)
-        let bar_x = (w as f32 * 110.0 / 1920.0).round() as u32;
-        let bar_y = (h as f32 * 87.0 / 1080.0).round() as u32;
-        let bar_w = (w as f32 * 20.0 / 1920.0).round().max(1.0) as u32;
-        let bar_h = (h as f32 * 1.0 / 1080.0).round().max(1.0) as u32;
+        Self {
+            primary: image.sub_image_rect(BAR.to_pixels(image)).average_color(),
+            secondary: image.sub_image_rect(MOUSE.to_pixels(image)).average_color(),
+        }
+    }
 
-        let mouse_x = (w as f32 * 146.0 / 1920.0).round() as u32;
-        let mouse_y = (h as f32 * 181.0 / 1080.0).round() as u32;
-        let mouse_w = (w as f32 * 14.0 / 1920.0).round().max(1.0) as u32;
-        let mouse_h = (h as f32 * 8.0 / 1080.0).round().max(1.0) as u32;
+    /// Same as [`Theme::from_options`], but fails if the first options entry
+    /// doesn't look hovered.
+    ///
+    /// The hover highlight is compared against the same spot one entry further
+    /// down; if both are alike, nothing is highlighted and `secondary` would
+    /// silently be the background color.
+    pub fn from_options_checked(image: Image) -> anyhow::Result<Self> {
+        // Min deviation between the hovered entry and its unhovered neighbour.
+        const MIN_HOVER_DEVIATION: f32 = 20.0;
 
-        Self {
-            primary: image.sub_image(bar_x, bar_y, bar_w, bar_h).average_color(),
-            secondary: image
-                .sub_image(mouse_x, mouse_y, mouse_w, mouse_h)
-                .average_color(),
+        let theme = Self::from_options(image);
+        let neighbour = image.sub_image_rect(MOUSE_NEIGHBOUR.to_pixels(image)).average_color();
+        if theme.secondary.deviation(neighbour) < MIN_HOVER_DEVIATION {
+            anyhow::bail!("no hover highlight found; hover the mouse over the first entry of the options list while sampling");
         }
+        Ok(theme)
     }
 }
@@ -1,6 +1,6 @@
 //! Miscellaneous CV utilities.
 
-use crate::{Image, Theme};
+use crate::{Image, RelativeRect, Theme};
 
 /// Try to read the party header player name.
 ///
@@ -9,46 +9,48 @@ use crate::{Image, Theme};
 /// positions from relative ratios so it works across different resolutions.
 ///
 /// Returns the first OCR string that looks non-empty.
-pub fn party_header_text(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> Option<String> {
-    // Reference capture the ratios were derived from.
-    const REF_W: f32 = 1920.0;
-    const REF_H: f32 = 1080.0;
-
-    // Avatar box and spacing (relative to 1080p reference).
-    let w = image.width() as f32;
-    let h = image.height() as f32;
-    let sx = w / REF_W;
-    let sy = h / REF_H;
-
-    let avatar_x = (96.0 * sx).round() as u32;
-    let avatar_y = (40.0 * sy).round() as u32;
-    let avatar_w = (94.0 * sx).round().max(1.0) as u32;
-    let avatar_h = (94.0 * sy).round().max(1.0) as u32;
-
-    let spacing_x = (324.0 * sx).round() as u32;
-    let spacing_y = (175.0 * sy).round() as u32;
+///
+/// With `roi` set (for HUDs whose elements were moved) the avatar scan is
+/// skipped and that region is read directly.
+pub fn party_header_text(image: Image, theme: Theme, ocr: &crate::ocr::Ocr, roi: Option<RelativeRect>) -> Option<String> {
+    if let Some(roi) = roi {
+        // User-entered, so clamp to the image like before rather than reject.
+        let text = image.sub_image_rect(roi.to_pixels(image)).get_text(theme, ocr);
+        let text = text.trim();
+        return (!text.is_empty()).then(|| text.to_string());
+    }
 
+    // First avatar box (relative to the 1080p reference).
+    const AVATAR: RelativeRect = RelativeRect::from_1080p(96.0, 40.0, 94.0, 94.0);
+    // Offset between avatars in the 2×2 grid (only x/y are used).
+    const SPACING: RelativeRect = RelativeRect::from_1080p(324.0, 175.0, 0.0, 0.0);
     // Player name region relative to avatar origin.
-    let name_x = (115.0 * sx).round() as u32;
-    let name_y = (124.0 * sy).round() as u32;
-    let name_w = (210.0 * sx).round().max(1.0) as u32;
-    let name_h = (24.0 * sy).round().max(1.0) as u32;
+    const NAME: RelativeRect = RelativeRect::from_1080p(115.0, 124.0, 210.0, 24.0);
+
+    let avatar = AVATAR.to_pixels(image);
+    let spacing = SPACING.to_pixels(image);
+    let name = NAME.to_pixels(image);
 
     // We scan the 2×2 grid of party avatars (up to 4 players).
     for i in 0..4 {
         let gx = i % 2;
         let gy = i / 2;
 
-        let x = avatar_x + gx * spacing_x;
-        let y = avatar_y + gy * spacing_y;
+        let x = avatar.x + gx * spacing.x;
+        let y = avatar.y + gy * spacing.y;
 
         // A quick color check to see if the avatar UI element is present.
-        let avatar_avg = image.sub_image(x, y, avatar_w, avatar_h).average_color();
+        let Some(avatar_img) = image.try_sub_image(x, y, avatar.w, avatar.h) else {
+            continue;
+        };
+        let avatar_avg = avatar_img.average_color();
         if avatar_avg.deviation(theme.primary) > 20.0 && avatar_avg.deviation(theme.secondary) > 20.0 {
             continue;
         }
 
-        let name_img = image.sub_image(x + name_x, y + name_y, name_w, name_h);
+        let Some(name_img) = image.try_sub_image(x + name.x, y + name.y, name.w, name.h) else {
+            continue;
+        };
         let text = name_img.get_text(theme, ocr);
         let text = text.trim().to_string();
         if !text.is_empty() {
@@ -58,3 +60,119 @@ pub fn party_header_text(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> O
 
     None
 }
+
+/// Relic tiers as shown in the (English) relic names.
+const RELIC_TIERS: [&str; 5] = ["Lith", "Meso", "Neo", "Axi", "Requiem"];
+
+/// Try to read the relic the local player is opening ("Meso N5").
+///
+/// The relic name sits below the player name of the first avatar on the
+/// reward screen. Returns the text from the relic tier onwards with a trailing
+/// "Relic" removed, or `None` if no tier name was read (other client languages
+/// or a hidden HUD).
+pub fn relic_name_text(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> Option<String> {
+    // Relic name region of the first avatar (relative to the 1080p reference).
+    const RELIC: RelativeRect = RelativeRect::from_1080p(211.0, 188.0, 210.0, 24.0);
+
+    let text = image.try_sub_image_rect(RELIC.to_pixels(image))?.get_text(theme, ocr);
+    let lower = text.to_ascii_lowercase();
+    let start = RELIC_TIERS
+        .iter()
+        .filter_map(|tier| lower.find(&tier.to_ascii_lowercase()))
+        .min()?;
+
+    let name = text[start..].trim();
+    let name = name.strip_suffix("Relic").unwrap_or(name).trim();
+    Some(name.to_string())
+}
+
+/// Mission types a void fissure can run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub enum MissionType {
+    Capture,
+    Exterminate,
+    Survival,
+    Defense,
+    MobileDefense,
+    Interception,
+    Excavation,
+    Rescue,
+    Sabotage,
+    Spy,
+    Disruption,
+    Hijack,
+    Defection,
+    Alchemy,
+    VoidCascade,
+    VoidFlood,
+    VoidArmageddon,
+}
+
+impl MissionType {
+    /// Names that contain another name ("Mobile Defense", "Void Cascade")
+    /// come first, so the most specific match wins.
+    const MATCH_ORDER: [MissionType; 17] = [
+        Self::MobileDefense,
+        Self::VoidCascade,
+        Self::VoidFlood,
+        Self::VoidArmageddon,
+        Self::Capture,
+        Self::Exterminate,
+        Self::Survival,
+        Self::Defense,
+        Self::Interception,
+        Self::Excavation,
+        Self::Rescue,
+        Self::Sabotage,
+        Self::Spy,
+        Self::Disruption,
+        Self::Hijack,
+        Self::Defection,
+        Self::Alchemy,
+    ];
+
+    /// English name as shown in the HUD.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Capture => "Capture",
+            Self::Exterminate => "Exterminate",
+            Self::Survival => "Survival",
+            Self::Defense => "Defense",
+            Self::MobileDefense => "Mobile Defense",
+            Self::Interception => "Interception",
+            Self::Excavation => "Excavation",
+            Self::Rescue => "Rescue",
+            Self::Sabotage => "Sabotage",
+            Self::Spy => "Spy",
+            Self::Disruption => "Disruption",
+            Self::Hijack => "Hijack",
+            Self::Defection => "Defection",
+            Self::Alchemy => "Alchemy",
+            Self::VoidCascade => "Void Cascade",
+            Self::VoidFlood => "Void Flood",
+            Self::VoidArmageddon => "Void Armageddon",
+        }
+    }
+}
+
+impl std::fmt::Display for MissionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Try to read the mission type from the objective text of the in-mission HUD.
+///
+/// Best effort and English only: the objective panel in the top-left corner
+/// is read and searched for a mission type name. `None` outside of missions,
+/// with the HUD hidden or when nothing matched.
+pub fn mission_type(image: Image, theme: Theme, ocr: &crate::ocr::Ocr) -> Option<MissionType> {
+    // Objective panel (relative to the 1080p reference).
+    const OBJECTIVE: RelativeRect = RelativeRect::from_1080p(60.0, 20.0, 520.0, 60.0);
+
+    let text = image.try_sub_image_rect(OBJECTIVE.to_pixels(image))?.get_text(theme, ocr);
+    let lower = text.to_ascii_lowercase();
+    MissionType::MATCH_ORDER
+        .into_iter()
+        .find(|mission| lower.contains(&mission.name().to_ascii_lowercase()))
+}
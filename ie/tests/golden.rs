@@ -0,0 +1,97 @@
+//! Golden-image tests for the relic reward detection.
+//!
+//! Every `tests/golden/*.json` sidecar names a capture (relative to the
+//! sidecar) and what it shows: the slot count follows from `names`, names may
+//! be off by up to `name_tolerance` edits (OCR noise), and `timer` is the
+//! countdown shown (0 if there is none). Runs with the `ocr/` models at the
+//! workspace root.
+
+use std::path::{Path, PathBuf};
+
+use ie::{Ie, OwnedImage, Theme};
+
+#[derive(serde::Deserialize)]
+struct Fixture {
+    image: PathBuf,
+    #[serde(default = "default_ui_scale")]
+    ui_scale: f32,
+    #[serde(default)]
+    theme: Option<Theme>,
+    timer: u32,
+    names: Vec<String>,
+    #[serde(default = "default_name_tolerance")]
+    name_tolerance: usize,
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+fn default_name_tolerance() -> usize {
+    2
+}
+
+fn engine(theme: Theme) -> Ie {
+    let ocr = Path::new(env!("CARGO_MANIFEST_DIR")).join("../ocr");
+    Ie::try_new(
+        ocr.join("detection.mnn"),
+        ocr.join("latin_recognition.mnn"),
+        ocr.join("latin_charset.txt"),
+        ie::DEFAULT_MIN_CONFIDENCE,
+        theme,
+    )
+    .expect("OCR models in ocr/ (see the model_downloader binary)")
+}
+
+fn fixtures() -> Vec<(PathBuf, Fixture)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut paths = std::fs::read_dir(&dir)
+        .expect("tests/golden")
+        .map(|entry| entry.expect("fixture entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let json = std::fs::read_to_string(&path).expect("read sidecar");
+            let fixture = serde_json::from_str(&json).unwrap_or_else(|err| panic!("{path:?}: {err}"));
+            (path, fixture)
+        })
+        .collect()
+}
+
+#[test]
+fn relic_reward_fixtures() {
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures in tests/golden");
+
+    let mut failures = Vec::new();
+    for (path, fixture) in fixtures {
+        let image = path.parent().expect("sidecar dir").join(&fixture.image);
+        let bytes = std::fs::read(&image).unwrap_or_else(|err| panic!("{image:?}: {err}"));
+        let img = OwnedImage::from_encoded(&bytes).unwrap_or_else(|err| panic!("{image:?}: {err:#}"));
+
+        let ie = engine(fixture.theme.unwrap_or(Theme::WHITE));
+        let analysis = ie.relicreward_analyze(&img, fixture.ui_scale);
+        let name = path.file_stem().map_or_else(String::new, |v| v.to_string_lossy().into_owned());
+
+        if analysis.rects.len() != fixture.names.len() {
+            failures.push(format!("{name}: {} slots, expected {}", analysis.rects.len(), fixture.names.len()));
+            continue;
+        }
+        for (i, expected) in fixture.names.iter().enumerate() {
+            let read = analysis.rewards.rewards.get(i).map_or("", |r| r.name.as_str());
+            let distance = levenshtein::levenshtein(&expected.to_lowercase(), &read.to_lowercase());
+            if distance > fixture.name_tolerance {
+                failures.push(format!("{name}: slot {i} read {read:?}, expected {expected:?}"));
+            }
+        }
+        if analysis.rewards.timer != fixture.timer {
+            failures.push(format!("{name}: timer {}, expected {}", analysis.rewards.timer, fixture.timer));
+        }
+    }
+
+    assert!(failures.is_empty(), "golden fixtures failed:\n{}", failures.join("\n"));
+}
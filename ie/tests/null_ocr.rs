@@ -0,0 +1,54 @@
+//! Detection and selection through [`Ie::with_ocr`] and [`Ocr::null`], on
+//! synthetic captures, without the OCR models.
+
+use ie::{Color, Ie, Ocr, OwnedImage, Rect, Theme};
+
+/// A 720p reward screen: four outlined 200px slots, the top-right corner
+/// of slot `selected` (if any) lit white.
+fn reward_screen(selected: Option<usize>) -> (OwnedImage, Vec<Rect>) {
+    let (width, height) = (1280, 720);
+    let mut img = OwnedImage::from_rgba(width, &[16, 22, 31, 255].repeat(width * height));
+
+    let slots = (0..4).map(|i| Rect::new(210 + i * 220, 230, 200, 200)).collect::<Vec<_>>();
+    for (i, slot) in slots.iter().enumerate() {
+        img.draw_rect(*slot, Color::new(215, 215, 215), 3);
+        if selected == Some(i) {
+            img.fill_rect(Rect::new(slot.right() - 25, slot.y, 25, 25), Color::WHITE, 1.0);
+        }
+    }
+
+    (img, slots)
+}
+
+fn engine() -> Ie {
+    Ie::with_ocr(Ocr::null(), Theme::WHITE)
+}
+
+#[test]
+fn detects_slots_without_models() {
+    let (img, slots) = reward_screen(None);
+    let analysis = engine().relicreward_analyze(&img, 1.0);
+
+    assert_eq!(analysis.rects.len(), slots.len());
+    for (found, slot) in analysis.rects.iter().zip(&slots) {
+        assert!(found.x.abs_diff(slot.x) <= 4 && found.w.abs_diff(slot.w) <= 8, "{found:?} vs {slot:?}");
+    }
+    assert_eq!(analysis.selected, None);
+    assert!(analysis.rewards.rewards.iter().all(|r| r.name.is_empty()));
+    assert_eq!(analysis.rewards.timer, 0);
+}
+
+#[test]
+fn detects_selection_without_models() {
+    let (img, _) = reward_screen(Some(1));
+    let analysis = engine().relicreward_analyze(&img, 1.0);
+    assert_eq!(analysis.selected, Some(1));
+}
+
+#[test]
+fn empty_screen_has_no_slots() {
+    let img = OwnedImage::from_rgba(1280, &[16, 22, 31, 255].repeat(1280 * 720));
+    let analysis = engine().relicreward_analyze(&img, 1.0);
+    assert!(analysis.rects.is_empty());
+    assert_eq!(analysis.selected, None);
+}
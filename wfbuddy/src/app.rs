@@ -1,20 +1,25 @@
 //! Iced application (Model-View-Update).
 
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use iced::widget::{
-	button, container, Button, Checkbox, Column, Container, PickList, Row, Scrollable, Text, TextInput,
+	button, container, slider, text, Button, Checkbox, Column, Container, PickList, Row, Scrollable, Text, TextInput,
 };
 use iced::{Element, Length, Subscription, Task};
 
-use crate::capture::{capture_by_app_name, list_windows, WindowInfo};
-use crate::config::Config;
+use crate::capture::{capture, capture_with, list_windows, matching_windows, CaptureTarget, WindowInfo, XcapBackend};
+use crate::config::{Config, RewardListStyle};
+use crate::events::{DetectionEvent, EventSink, JsonLinesServer};
+use crate::stats::Stats;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tab {
     RelicRewards,
+    MissionRewards,
+    PriceCheck,
     Settings,
     Debug,
 }
@@ -23,6 +28,8 @@ impl std::fmt::Display for Tab {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Tab::RelicRewards => write!(f, "Relic Rewards"),
+            Tab::MissionRewards => write!(f, "Mission Rewards"),
+            Tab::PriceCheck => write!(f, "Price Check"),
             Tab::Settings => write!(f, "Settings"),
             Tab::Debug => write!(f, "Debug"),
         }
@@ -41,27 +48,121 @@ pub enum Message {
 
     AppNameChanged(String),
     PollDelayChanged(String),
+    RewardGraceChanged(String),
+    AutoCheckChanged(String),
     MaxCaptureHeightChanged(String),
+    WebhookUrlChanged(String),
+    FormaPlatChanged(String),
+    MinPlatChanged(String),
+    PartyHeaderRoiChanged(String),
+    MonitorFallbackToggled(bool),
+    DesktopDuplicationToggled(bool),
+    CountDuplicatesOnceToggled(bool),
+    EeLogTriggerToggled(bool),
+    MultiClientToggled(bool),
+    ReadMissionTypeToggled(bool),
+    OfflineToggled(bool),
+    MatchWindowTitleToggled(bool),
+    RewardListStylePicked(RewardListStyle),
+    ResetRewardListSettings,
+    LanguagePicked(data::Language),
+    MinConfidenceChanged(f32),
+    MinConfidenceReleased,
+    UiScaleChanged(f32),
+    BlankThresholdChanged(u8),
+    BlankThresholdReleased,
+    BlankThresholdApplied(Result<(), String>),
+    RewardFontScaleChanged(f32),
+    CalibrateUiScale,
+    UiScaleCalibrated(Result<Option<f32>, String>),
+    OcrAssetsLoaded(Result<Option<ie::OcrAssetSource>, String>),
 
     SaveConfig,
+    ResetStats,
+    CopyRewards,
     ConfigSaved(Result<(), String>),
+    StatsSaved(Result<(), String>),
 
     SampleTheme,
     ThemeSampled(Result<ie::Theme, String>),
+    ValidateTheme,
+    ThemeValidated(Result<Option<ie::screen::relicreward::ThemeValidation>, String>),
+
+    DetectSlotsNow,
+    RunSelfTest,
+    SelfTestDone(Result<ie::SelfTestReport, String>),
+    SlotsDetected(Result<Vec<ie::Rect>, String>),
 
     PollPartyHeaderNow,
-    PartyHeaderPolled(Result<Option<String>, String>),
+    PartyHeaderPolled(Result<Option<(String, Option<WindowInfo>)>, String>),
+    RelicNamePolled(Result<Option<String>, String>),
+    MissionTypePolled(Result<Option<ie::util::MissionType>, String>),
+
+    ScreenshotPathChanged(String),
+    PriceSearchChanged(String),
+    AnalyzeScreenshot,
+    ScreenshotAnalyzed(Result<RelicPollResult, String>),
 
     PollRelicNow,
+    RelicCaptured(Result<Arc<ie::OwnedImage>, String>),
     RelicPolled(Result<RelicPollResult, String>),
 
+    PollMissionNow,
+    MissionPolled(Result<ie::screen::missionreward::MissionRewards, String>),
+
     ValuedFormaToggled(bool),
+    OpenMarket(String),
 }
 
 #[derive(Debug, Clone)]
 pub struct RelicPollResult {
     pub rewards: ie::screen::relicreward::Rewards,
     pub selected: Option<usize>,
+    /// Where the slots were found, to narrow the search on the next frame.
+    pub slot_hint: Option<ie::screen::relicreward::SlotHint>,
+}
+
+/// Timing of the relic reward poll stages, shown in the Debug tab.
+#[derive(Debug, Default)]
+struct PollMetrics {
+    /// Smoothed time from starting a capture to receiving the frame.
+    capture: Option<Duration>,
+    /// Smoothed time from starting an analysis (OCR included) to its result.
+    analysis: Option<Duration>,
+    /// When the most recent analyses finished, for the effective frame rate.
+    analyzed_at: VecDeque<Instant>,
+}
+
+/// Number of recent analyses the frame rate is averaged over.
+const METRICS_FRAMES: usize = 10;
+
+impl PollMetrics {
+    fn record_capture(&mut self, took: Duration) {
+        self.capture = Some(smooth(self.capture, took));
+    }
+
+    fn record_analysis(&mut self, took: Duration, now: Instant) {
+        self.analysis = Some(smooth(self.analysis, took));
+        if self.analyzed_at.len() == METRICS_FRAMES {
+            self.analyzed_at.pop_front();
+        }
+        self.analyzed_at.push_back(now);
+    }
+
+    /// Analyzed frames per second over the recent window (`None` until two frames).
+    fn fps(&self) -> Option<f32> {
+        let (first, last) = (self.analyzed_at.front()?, self.analyzed_at.back()?);
+        let span = last.duration_since(*first).as_secs_f32();
+        (self.analyzed_at.len() > 1 && span > 0.0).then(|| (self.analyzed_at.len() - 1) as f32 / span)
+    }
+}
+
+/// Exponential moving average, so single slow frames don't dominate.
+fn smooth(prev: Option<Duration>, sample: Duration) -> Duration {
+    match prev {
+        Some(prev) => prev.mul_f32(0.8) + sample.mul_f32(0.2),
+        None => sample,
+    }
 }
 
 #[derive(Debug)]
@@ -71,9 +172,30 @@ struct PollState {
 
     reward_mode_until: Option<Instant>,
     next_reward_poll: Instant,
-    reward_in_flight: bool,
+    // Capture and analysis run as separate stages so a slow OCR pass doesn't
+    // push back the next capture. At most one captured frame waits for
+    // analysis; a newer capture replaces it. The gain over the old serial
+    // capture+OCR loop shows in the Debug tab's capture/analysis latency
+    // (`metrics`); the effective frame rate is no longer capped at
+    // 1 / (capture + analysis).
+    reward_capture_in_flight: bool,
+    reward_analysis_in_flight: bool,
+    pending_reward_frame: Option<Arc<ie::OwnedImage>>,
+    reward_capture_started: Option<Instant>,
+    reward_analysis_started: Option<Instant>,
+    metrics: PollMetrics,
+    /// Client the last reward or mission header was read from (multi-client only).
+    source_window: Option<WindowInfo>,
+
+    mission_in_flight: bool,
+
+    mission_type_in_flight: bool,
+    next_mission_type_poll: Instant,
 }
 
+/// Interval of the in-mission HUD read for the mission type.
+const MISSION_TYPE_POLL: Duration = Duration::from_secs(5);
+
 impl PollState {
     fn new(now: Instant) -> Self {
         Self {
@@ -81,7 +203,16 @@ impl PollState {
             party_in_flight: false,
             reward_mode_until: None,
             next_reward_poll: now,
-            reward_in_flight: false,
+            reward_capture_in_flight: false,
+            reward_analysis_in_flight: false,
+            pending_reward_frame: None,
+            reward_capture_started: None,
+            reward_analysis_started: None,
+            metrics: PollMetrics::default(),
+            source_window: None,
+            mission_in_flight: false,
+            mission_type_in_flight: false,
+            next_mission_type_poll: now,
         }
     }
 }
@@ -89,36 +220,177 @@ impl PollState {
 #[derive(Debug, Default)]
 struct RelicState {
     rewards: Option<ie::screen::relicreward::Rewards>,
+    // Slots of the last analyzed frame; the next frame searches around them first.
+    slot_hint: Option<ie::screen::relicreward::SlotHint>,
+    // Relic being opened ("Meso N5"), read once when the reward screen shows up.
+    relic_name: Option<String>,
+    // Mission type last read from the in-mission HUD; kept for the reward screen,
+    // which doesn't show it.
+    mission_type: Option<ie::util::MissionType>,
+    // Recent raw detections of the current reward screen, voted into `rewards`.
+    history: VecDeque<ie::screen::relicreward::Rewards>,
     selected: Option<usize>,
+    // Latest pick on the current reward screen. The player can move it until
+    // the timer runs out, so it is only added to the totals once the screen closes.
+    last_pick: Option<(ie::screen::relicreward::Rewards, usize)>,
     valued_forma: bool,
     last_updated: Option<Instant>,
+
+    // Totals of the rewards selected since the app started.
+    session_platinum: f32,
+    session_ducats: u32,
+
+    // Rewards picked on earlier reward screens this session, by `pick_key`.
+    // The game's owned count already includes them, so they are subtracted
+    // to show what was owned before the session.
+    picked: HashMap<String, u32>,
+    // Pick of the last closed screen; the owned count shown for it doesn't
+    // include it yet, so it moves into `picked` when the next reward screen opens.
+    pending_pick: Option<String>,
+}
+
+/// Number of recent frames voted over per reward slot.
+const VOTE_FRAMES: usize = 5;
+
+impl RelicState {
+    /// Add a raw detection to the history and return the voted rewards.
+    ///
+    /// Per slot the most frequent non-empty name across the last
+    /// [`VOTE_FRAMES`] frames wins (ties go to the most confident read), and
+    /// the most confident read of that name supplies the owned count. The
+    /// history resets when the slot count changes, i.e. on a different screen.
+    fn vote(&mut self, rewards: ie::screen::relicreward::Rewards) -> ie::screen::relicreward::Rewards {
+        if rewards.rewards.is_empty() {
+            self.history.clear();
+            return rewards;
+        }
+        if self.history.back().is_some_and(|prev| prev.rewards.len() != rewards.rewards.len()) {
+            self.history.clear();
+        }
+        if self.history.len() == VOTE_FRAMES {
+            self.history.pop_front();
+        }
+        self.history.push_back(rewards.clone());
+
+        let mut voted = rewards;
+        for (slot, reward) in voted.rewards.iter_mut().enumerate() {
+            let reads = || self.history.iter().filter_map(|frame| frame.rewards.get(slot)).filter(|r| !r.name.is_empty());
+
+            let best = reads().max_by(|a, b| {
+                let count = |name: &str| reads().filter(|r| r.name == name).count();
+                count(&a.name)
+                    .cmp(&count(&b.name))
+                    .then(a.confidence.total_cmp(&b.confidence))
+            });
+            if let Some(best) = best {
+                *reward = best.clone();
+            }
+        }
+        // Slots were voted independently, so the per-frame duplicate counts are stale.
+        voted.mark_duplicates();
+        voted
+    }
+}
+
+/// Market/relic info for a detected reward name (`None` when unknown).
+#[derive(Debug, Default, Clone)]
+struct ItemInfo {
+    /// Known item name the OCR text was matched to (`None` when unreadable).
+    matched_name: Option<String>,
+    /// The match was a fuzzy guess rather than an exact read.
+    fuzzy: bool,
+    market_slug: Option<String>,
+    platinum: Option<f32>,
+    ducats: Option<u32>,
+    vaulted: Option<bool>,
+    relic: Option<bool>,
+    /// Warframe/weapon/companion the part belongs to, from PublicExport.
+    category: Option<data::ItemCategory>,
+    /// Localized names of the currently-dropping relics that contain this item.
+    active_relics: Vec<String>,
+    /// Item data is loaded but the name isn't in it (bad read or an item newer
+    /// than the data); valued at zero and tagged in the reward list.
+    unknown: bool,
+}
+
+#[derive(Debug, Default)]
+struct MissionState {
+    rewards: Option<ie::screen::missionreward::MissionRewards>,
+    last_updated: Option<Instant>,
+    /// The reward list of the summary screen currently shown was read, so
+    /// further polls seeing the screen don't read it again.
+    summary_read: bool,
+}
+
+/// Stack size to value a reward with. Only forma drops stacked from relics,
+/// so a stack read for anything else is an OCR misread and ignored.
+fn trusted_stack(is_forma: bool, stack: u32) -> u32 {
+    if is_forma { stack.max(1) } else { 1 }
+}
+
+/// Key of a reward in [`RelicState::picked`]: the matched item name, or the
+/// raw read when it didn't match.
+fn pick_key(info: &ItemInfo, raw_name: &str) -> String {
+    info.matched_name.as_deref().unwrap_or(raw_name).trim().to_lowercase()
+}
+
+/// Display values of one reward slot (see [`App::reward_row`]).
+struct RewardRow {
+    info: ItemInfo,
+    name: String,
+    platinum: f32,
+    ducats: u32,
+    /// The item has a market price. Requiem and event relic drops (Kuva,
+    /// Requiem mods, ...) don't, and show as "no market value" rather than 0p.
+    priced: bool,
+    /// OCR'd owned count minus this session's earlier picks.
+    owned_before: u32,
+    /// Times this item was picked this session (including the current screen).
+    picked: u32,
 }
 
 #[derive(Debug, Default)]
 struct DebugState {
     last_party_header: Option<String>,
+    reward_slots: Option<Vec<ie::Rect>>,
+    self_test: Option<String>,
+    ocr_asset_source: Option<ie::OcrAssetSource>,
 }
 
 pub struct App {
     tab: Tab,
 
     config: Config,
+    stats: Stats,
 
     // Editable fields (text inputs)
     app_name_input: String,
     poll_delay_input: String,
+    reward_grace_input: String,
+    auto_check_input: String,
     max_capture_height_input: String,
+    webhook_url_input: String,
+    forma_plat_input: String,
+    min_plat_input: String,
+    party_header_roi_input: String,
+    screenshot_path_input: String,
+    price_search_input: String,
 
     windows: Vec<WindowInfo>,
     selected_window: Option<WindowInfo>,
 
     status: Option<String>,
+    /// Warning from the last theme check, shown in Settings until the theme is resampled.
+    theme_warning: Option<String>,
 
     ie: Arc<Mutex<ie::Ie>>,
     data: Option<data::Data>,
+    events: Option<Box<dyn EventSink>>,
+    log_tail: Option<crate::logwatcher::LogTail>,
 
     poll: PollState,
     relic: RelicState,
+    mission: MissionState,
     debug: DebugState,
 }
 
@@ -132,21 +404,57 @@ impl App {
     fn boot() -> (Self, Task<Message>) {
         let cfg = Config::load_or_default();
 
-        let detection = resolve_model_path("ocr/detection.mnn");
-        let recognition = resolve_model_path("ocr/latin_recognition.mnn");
-        let charsset = resolve_model_path("ocr/latin_charset.txt");
+        let (detection, recognition, charsset) = resolve_ocr_assets(cfg.client_language);
 
-        let ie = ie::Ie::new(detection, recognition, charsset, cfg.theme);
-        let ie = Arc::new(Mutex::new(ie));
+        let mut ie = ie::Ie::new_lenient(detection, recognition, charsset, cfg.min_confidence, cfg.theme);
+        ie.set_blank_threshold(cfg.blank_threshold);
+        ie.set_selection_probe(cfg.selection_probe);
+        let status = ie.ocr_error().map(|err| {
+            tracing::warn!(error = %err, "OCR unavailable; running without text recognition");
+            format!("OCR unavailable, only screen detection works: {err}")
+        });
+        let ocr_asset_source = ie.ocr_asset_source().cloned();
 
         // Data loading can fail (network/offline). We keep the app usable without it.
-        let data = match data::Data::populated(data::Language::English) {
+        data::set_offline(cfg.is_offline());
+        let data = match data::Data::populated(cfg.client_language) {
             Ok(d) => Some(d),
+            Err(err) if cfg.is_offline() => {
+                tracing::info!(error = %err, "offline mode without cached data; ducat/vaulted info disabled");
+                None
+            }
             Err(err) => {
                 tracing::warn!(error = %err, "failed to load data; ducat/vaulted info disabled");
                 None
             }
         };
+        ie.set_stackable_names(
+            data.iter()
+                .filter_map(|d| d.forma_blueprint_name())
+                .map(str::to_string)
+                .collect(),
+        );
+        let ie = Arc::new(Mutex::new(ie));
+
+        let events = cfg.event_server_port.and_then(|port| match JsonLinesServer::bind(port) {
+            Ok(server) => Some(Box::new(server) as Box<dyn EventSink>),
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to start event server");
+                None
+            }
+        });
+
+        let log_tail = cfg
+            .ee_log_trigger
+            .then(|| cfg.ee_log_path.clone().or_else(crate::logwatcher::default_log_path))
+            .flatten()
+            .and_then(|path| match crate::logwatcher::LogTail::open(&path) {
+                Ok(tail) => Some(tail),
+                Err(err) => {
+                    tracing::warn!(error = %err, "failed to open EE.log; log trigger disabled");
+                    None
+                }
+            });
 
         let windows = match list_windows() {
             Ok(v) => v,
@@ -158,7 +466,7 @@ impl App {
 
         let selected_window = windows
             .iter()
-            .find(|w| w.app_name == cfg.app_name)
+            .find(|w| w.matches(&cfg.app_name, cfg.match_window_title))
             .cloned();
 
         let now = Instant::now();
@@ -167,22 +475,41 @@ impl App {
             tab: Tab::RelicRewards,
             app_name_input: cfg.app_name.clone(),
             poll_delay_input: cfg.poll_delay_s.to_string(),
+            reward_grace_input: cfg.reward_grace_ms.to_string(),
+            auto_check_input: cfg.auto_check_ms.to_string(),
             max_capture_height_input: cfg
                 .max_capture_height
                 .map(|v| v.to_string())
                 .unwrap_or_default(),
+            webhook_url_input: cfg.webhook_url.clone().unwrap_or_default(),
+            forma_plat_input: cfg.forma_plat_override.map(|v| v.to_string()).unwrap_or_default(),
+            min_plat_input: if cfg.min_plat > 0.0 { cfg.min_plat.to_string() } else { String::new() },
+            screenshot_path_input: String::new(),
+            price_search_input: String::new(),
+            party_header_roi_input: cfg
+                .party_header_roi
+                .map(|r| format!("{}, {}, {}, {}", r.x, r.y, r.w, r.h))
+                .unwrap_or_default(),
 
             windows,
             selected_window,
 
-            status: None,
+            status,
+            theme_warning: None,
             ie,
             data,
+            events,
+            log_tail,
 
             poll: PollState::new(now),
             relic: RelicState::default(),
-            debug: DebugState::default(),
+            mission: MissionState::default(),
+            debug: DebugState {
+                ocr_asset_source,
+                ..Default::default()
+            },
             config: cfg,
+            stats: Stats::load_or_default(),
         };
 
         (app, Task::none())
@@ -213,7 +540,7 @@ impl App {
                         self.selected_window = self
                             .windows
                             .iter()
-                            .find(|w| w.app_name == self.config.app_name)
+                            .find(|w| w.matches(&self.config.app_name, self.config.match_window_title))
                             .cloned();
                         self.status = Some("Window list refreshed.".into());
                     }
@@ -226,8 +553,8 @@ impl App {
 
             Message::WindowPicked(win) => {
                 self.selected_window = Some(win.clone());
-                self.config.app_name = win.app_name.clone();
-                self.app_name_input = win.app_name;
+                self.config.app_name = win.target_name().to_string();
+                self.app_name_input = win.target_name().to_string();
                 self.status = Some("Window selected.".into());
                 Task::none()
             }
@@ -246,17 +573,227 @@ impl App {
                 Task::none()
             }
 
+            Message::RewardGraceChanged(v) => {
+                self.reward_grace_input = v.clone();
+                if let Ok(parsed) = v.trim().parse::<u64>() {
+                    let range = crate::config::REWARD_GRACE_MS;
+                    self.config.reward_grace_ms = parsed.clamp(*range.start(), *range.end());
+                }
+                Task::none()
+            }
+
+            Message::AutoCheckChanged(v) => {
+                self.auto_check_input = v.clone();
+                if let Ok(parsed) = v.trim().parse::<u64>() {
+                    let range = crate::config::AUTO_CHECK_MS;
+                    self.config.auto_check_ms = parsed.clamp(*range.start(), *range.end());
+                }
+                Task::none()
+            }
+
             Message::MaxCaptureHeightChanged(v) => {
                 self.max_capture_height_input = v.clone();
                 self.config.max_capture_height = v.trim().parse::<u32>().ok();
                 Task::none()
             }
 
+            Message::LanguagePicked(lang) => {
+                if lang == self.config.client_language {
+                    return Task::none();
+                }
+                self.config.client_language = lang;
+                self.reload_ocr()
+            }
+
+            Message::MinConfidenceChanged(v) => {
+                self.config.min_confidence = v;
+                Task::none()
+            }
+
+            Message::UiScaleChanged(v) => {
+                self.config.ui_scale = v;
+                Task::none()
+            }
+
+            Message::BlankThresholdChanged(v) => {
+                self.config.blank_threshold = v;
+                Task::none()
+            }
+
+            // Applied once the slider is let go, and off the UI thread: the
+            // engine stays locked for a whole analysis while polling.
+            Message::BlankThresholdReleased => {
+                let ie = self.ie.clone();
+                let threshold = self.config.blank_threshold;
+                Task::perform(
+                    async move {
+                        let mut guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                        guard.set_blank_threshold(threshold);
+                        Ok(())
+                    },
+                    Message::BlankThresholdApplied,
+                )
+            }
+
+            Message::BlankThresholdApplied(res) => {
+                if let Err(err) = res {
+                    self.status = Some(format!("Failed to apply the blank frame threshold: {err}"));
+                }
+                Task::none()
+            }
+
+            Message::RewardFontScaleChanged(v) => {
+                self.config.reward_font_scale = v;
+                Task::none()
+            }
+
+            Message::CalibrateUiScale => {
+                let target = self.config.capture_target();
+                let ie = self.ie.clone();
+                Task::perform(
+                    async move {
+                        let img = capture(&target).map_err(|e| e.to_string())?;
+                        let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                        Ok(guard.estimate_ui_scale(&img))
+                    },
+                    Message::UiScaleCalibrated,
+                )
+            }
+
+            Message::UiScaleCalibrated(res) => {
+                match res {
+                    Ok(Some(scale)) => {
+                        self.config.ui_scale = scale;
+                        self.status = Some(format!("HUD scale calibrated: {:.0}%", scale * 100.0));
+                    }
+                    Ok(None) => {
+                        self.status = Some("HUD scale calibration needs the relic reward screen open.".into());
+                    }
+                    Err(err) => self.status = Some(format!("HUD scale calibration failed: {err}")),
+                }
+                Task::none()
+            }
+
+            // Rebuilding the engine is expensive, so only do it once the slider is let go.
+            Message::MinConfidenceReleased => self.reload_ocr(),
+
+            Message::OcrAssetsLoaded(res) => {
+                match res {
+                    Ok(source) => {
+                        self.debug.ocr_asset_source = source;
+                        self.status = Some("OCR engine reloaded. Item names follow a language change after a restart.".into())
+                    }
+                    Err(err) => self.status = Some(format!("Failed to load OCR models, keeping previous: {err}")),
+                }
+                Task::none()
+            }
+
+            Message::WebhookUrlChanged(v) => {
+                let url = v.trim();
+                self.config.webhook_url = (!url.is_empty()).then(|| url.to_string());
+                self.webhook_url_input = v;
+                Task::none()
+            }
+
+            Message::FormaPlatChanged(v) => {
+                self.forma_plat_input = v.clone();
+                self.config.forma_plat_override = v.trim().parse::<f32>().ok().filter(|v| *v >= 0.0);
+                Task::none()
+            }
+
+            Message::MinPlatChanged(v) => {
+                self.min_plat_input = v.clone();
+                self.config.min_plat = v.trim().parse::<f32>().ok().filter(|v| *v >= 0.0).unwrap_or(0.0);
+                Task::none()
+            }
+
+            Message::PartyHeaderRoiChanged(v) => {
+                self.config.party_header_roi = parse_relative_rect(&v);
+                self.party_header_roi_input = v;
+                Task::none()
+            }
+
+            Message::MonitorFallbackToggled(v) => {
+                self.config.monitor_capture_fallback = v;
+                Task::none()
+            }
+
+            Message::DesktopDuplicationToggled(v) => {
+                self.config.desktop_duplication = v;
+                Task::none()
+            }
+
+            Message::CountDuplicatesOnceToggled(v) => {
+                self.config.count_duplicates_once = v;
+                Task::none()
+            }
+
+            Message::EeLogTriggerToggled(v) => {
+                self.config.ee_log_trigger = v;
+                Task::none()
+            }
+
+            Message::MatchWindowTitleToggled(v) => {
+                self.config.match_window_title = v;
+                Task::none()
+            }
+
+            Message::OfflineToggled(v) => {
+                self.config.offline = v;
+                Task::none()
+            }
+
+            Message::MultiClientToggled(v) => {
+                self.config.multi_client = v;
+                self.poll.source_window = None;
+                Task::none()
+            }
+
+            Message::ReadMissionTypeToggled(v) => {
+                self.config.read_mission_type = v;
+                if !v {
+                    self.relic.mission_type = None;
+                }
+                Task::none()
+            }
+
+            Message::RewardListStylePicked(style) => {
+                self.config.reward_list_style = style;
+                Task::none()
+            }
+
+            Message::ResetRewardListSettings => {
+                self.config.reset_reward_list_defaults();
+                self.min_plat_input.clear();
+                Task::none()
+            }
+
             Message::SaveConfig => {
                 let cfg = self.config.clone();
                 Task::perform(async move { cfg.save().map_err(|e| e.to_string()) }, Message::ConfigSaved)
             }
 
+            Message::CopyRewards => match self.rewards_text() {
+                Some(text) => {
+                    self.status = Some("Rewards copied to the clipboard.".into());
+                    iced::clipboard::write(text)
+                }
+                None => Task::none(),
+            },
+
+            Message::ResetStats => {
+                self.stats = Stats::default();
+                self.save_stats()
+            }
+
+            Message::StatsSaved(res) => {
+                if let Err(err) = res {
+                    tracing::warn!(error = %err, "failed to save stats");
+                    self.status = Some(format!("Stats save failed: {err}"));
+                }
+                Task::none()
+            }
+
             Message::ConfigSaved(res) => {
                 match res {
                     Ok(_) => self.status = Some("Config saved.".into()),
@@ -266,13 +803,12 @@ impl App {
             }
 
             Message::SampleTheme => {
-                let app_name = self.config.app_name.clone();
-                let max_h = self.config.max_capture_height;
+                let target = self.config.capture_target();
                 let ie = self.ie.clone();
                 Task::perform(
                     async move {
-                        let img = capture_by_app_name(&app_name, max_h).map_err(|e| e.to_string())?;
-                        let theme = ie::Theme::from_options(img.as_image());
+                        let img = capture(&target).map_err(|e| e.to_string())?;
+                        let theme = ie::Theme::from_options_checked(img.as_image()).map_err(|e| e.to_string())?;
                         // Update engine theme immediately.
                         if let Ok(mut guard) = ie.lock() {
                             guard.set_theme(theme);
@@ -287,8 +823,10 @@ impl App {
                 match res {
                     Ok(theme) => {
                         self.config.theme = theme;
+                        self.theme_warning = None;
                         self.status = Some(format!(
-                            "Theme sampled: primary=({}, {}, {}), secondary=({}, {}, {})",
+                            "Theme sampled: primary=({}, {}, {}), secondary=({}, {}, {}). \
+                             Check it on the next relic reward screen.",
                             theme.primary.r,
                             theme.primary.g,
                             theme.primary.b,
@@ -302,24 +840,136 @@ impl App {
                 Task::none()
             }
 
+            Message::ValidateTheme => {
+                let target = self.config.capture_target();
+                let ie = self.ie.clone();
+                Task::perform(
+                    async move {
+                        let img = capture(&target).map_err(|e| e.to_string())?;
+                        let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                        Ok(guard.validate_theme_against(&img))
+                    },
+                    Message::ThemeValidated,
+                )
+            }
+
+            Message::ThemeValidated(res) => {
+                match res {
+                    Ok(Some(v)) => {
+                        let c = v.text_color;
+                        let summary = format!(
+                            "reward text ({}, {}, {}), deviation from primary {:.1}, secondary {:.1}",
+                            c.r, c.g, c.b, v.primary_deviation, v.secondary_deviation
+                        );
+                        if v.matches() {
+                            self.theme_warning = None;
+                            self.status = Some(format!("Theme matches the reward screen: {summary}"));
+                        } else {
+                            tracing::warn!(%summary, "theme doesn't match the reward screen");
+                            self.theme_warning = Some(format!(
+                                "Theme doesn't match the reward screen ({summary}). \
+                                 It was probably sampled on the wrong screen; resample it."
+                            ));
+                            self.status = Some("Theme check failed, see Settings.".into());
+                        }
+                    }
+                    Ok(None) => self.status = Some("Theme check needs the relic reward screen open.".into()),
+                    Err(err) => self.status = Some(format!("Theme check failed: {err}")),
+                }
+                Task::none()
+            }
+
+            Message::DetectSlotsNow => {
+                let target = self.config.capture_target();
+                let ui_scale = self.config.ui_scale;
+                let ie = self.ie.clone();
+                Task::perform(
+                    async move {
+                        let img = capture(&target).map_err(|e| e.to_string())?;
+                        let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                        Ok(guard.relicreward_detect_slots(&img, ui_scale))
+                    },
+                    Message::SlotsDetected,
+                )
+            }
+
+            Message::SlotsDetected(res) => {
+                match res {
+                    Ok(slots) => self.debug.reward_slots = Some(slots),
+                    Err(err) => self.status = Some(format!("Slot detection failed: {err}")),
+                }
+                Task::none()
+            }
+
+            Message::PriceSearchChanged(v) => {
+                self.price_search_input = v;
+                Task::none()
+            }
+
+            Message::RunSelfTest => {
+                let ie = self.ie.clone();
+                Task::perform(
+                    async move {
+                        let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                        Ok(guard.self_test())
+                    },
+                    Message::SelfTestDone,
+                )
+            }
+
+            Message::SelfTestDone(res) => {
+                self.debug.self_test = Some(match res {
+                    Ok(report) => report.to_string(),
+                    Err(err) => format!("Self-test failed to run: {err}"),
+                });
+                Task::none()
+            }
+
             Message::PollPartyHeaderNow => {
                 if self.poll.party_in_flight {
                     return Task::none();
                 }
                 self.poll.party_in_flight = true;
 
-                let app_name = self.config.app_name.clone();
-                let max_h = self.config.max_capture_height;
+                let target = self.config.capture_target();
+                let multi_client = self.config.multi_client;
+                let relic_header = self.config.client_language.party_header_match_string();
+                let roi = self.config.party_header_roi;
                 let ie = self.ie.clone();
 
                 Task::perform(
                     async move {
-                        let img = capture_by_app_name(&app_name, max_h).map_err(|e| e.to_string())?;
-                        let text = ie
-                            .lock()
-                            .map_err(|_| "IE mutex poisoned".to_string())?
-                            .util_party_header_text(&img);
-                        Ok(text)
+                        // One backend for the whole poll, so the windows are only enumerated once.
+                        let backend = XcapBackend::for_target(&target);
+                        let windows = match multi_client {
+                            true => matching_windows(&backend, &target)
+                                .map_err(|e| e.to_string())?
+                                .into_iter()
+                                .map(Some)
+                                .collect(),
+                            false => vec![None],
+                        };
+
+                        // With several clients, report the first one showing a
+                        // reward screen, otherwise the first header read at all.
+                        let mut first = None;
+                        for window in windows {
+                            let target = window.as_ref().map_or_else(|| target.clone(), |w| target.for_window(w.id));
+                            let img = capture_with(&backend, &target).map_err(|e| e.to_string())?;
+                            let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                            let text = guard.util_party_header_text(&img, roi);
+                            if let Some(text) = text.as_ref().filter(|t| t.to_ascii_lowercase().contains(relic_header)) {
+                                return Ok(Some((text.clone(), window)));
+                            }
+                            // The mission summary has no party header, it is recognized by its own title.
+                            if guard.missionreward_is_screen(&img) {
+                                return Ok(Some((ie::screen::missionreward::HEADER.to_string(), window)));
+                            }
+                            if let Some(text) = text {
+                                first.get_or_insert((text, window));
+                            }
+                        }
+                        Ok(first)
                     },
                     Message::PartyHeaderPolled,
                 )
@@ -329,16 +979,37 @@ impl App {
                 self.poll.party_in_flight = false;
 
                 match res {
-                    Ok(Some(text)) => {
+                    Ok(Some((text, window))) => {
                         self.debug.last_party_header = Some(text.clone());
-                        self.status = Some(format!("Party header: {text}"));
+                        self.status = Some(match &window {
+                            Some(window) => format!("Party header ({window}): {text}"),
+                            None => format!("Party header: {text}"),
+                        });
+
+                        // The poll reports the mission summary by its title; read its reward
+                        // list instead, once per appearance of the screen.
+                        let header = text.to_ascii_lowercase();
+                        if header.contains(ie::screen::missionreward::HEADER) {
+                            if self.mission.summary_read {
+                                return Task::none();
+                            }
+                            self.poll.source_window = window;
+                            return self.update(Message::PollMissionNow);
+                        }
+                        self.mission.summary_read = false;
+
+                        if !header.contains(self.config.client_language.party_header_match_string()) {
+                            return Task::none();
+                        }
 
-                        // Enter reward-mode for a short window to auto-refresh rewards.
-                        let now = Instant::now();
-                        self.poll.reward_mode_until = Some(now + Duration::from_secs(3));
-                        self.poll.next_reward_poll = now;
+                        // Keep following the client already in reward mode.
+                        if self.poll.reward_mode_until.is_none() {
+                            self.poll.source_window = window;
+                        }
+                        return self.enter_reward_mode();
                     }
                     Ok(None) => {
+                        self.mission.summary_read = false;
                         self.status = Some("Party header: <none>".into());
                     }
                     Err(err) => {
@@ -349,33 +1020,92 @@ impl App {
                 Task::none()
             }
 
-            Message::PollRelicNow => {
-                if self.poll.reward_in_flight {
-                    return Task::none();
-                }
-                self.poll.reward_in_flight = true;
+            Message::ScreenshotPathChanged(v) => {
+                self.screenshot_path_input = v;
+                Task::none()
+            }
 
-                let app_name = self.config.app_name.clone();
-                let max_h = self.config.max_capture_height;
+            Message::AnalyzeScreenshot => {
+                // Pasted paths often come quoted (e.g. "Copy as path" on Windows).
+                let path = PathBuf::from(self.screenshot_path_input.trim().trim_matches('"'));
+                let ui_scale = self.config.ui_scale;
                 let ie = self.ie.clone();
-
                 Task::perform(
                     async move {
-                        let img = capture_by_app_name(&app_name, max_h).map_err(|e| e.to_string())?;
                         let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
-                        let rewards = guard.relicreward_get_rewards(&img);
-                        let selected = guard.relicreward_get_selected(&img);
-                        Ok(RelicPollResult { rewards, selected })
+                        let analysis = guard.analyze_file(&path, ui_scale).map_err(|e| format!("{e:#}"))?;
+                        Ok(RelicPollResult {
+                            rewards: analysis.rewards,
+                            selected: analysis.selected,
+                            slot_hint: None,
+                        })
                     },
-                    Message::RelicPolled,
+                    Message::ScreenshotAnalyzed,
+                )
+            }
+
+            Message::ScreenshotAnalyzed(res) => {
+                match res {
+                    Ok(v) => {
+                        // A still image isn't part of the live frame sequence.
+                        self.relic.history.clear();
+                        self.relic.rewards = Some(v.rewards);
+                        self.relic.selected = v.selected;
+                        self.relic.last_updated = Some(Instant::now());
+                        self.status = Some("Screenshot analyzed.".into());
+                    }
+                    Err(err) => self.status = Some(format!("Screenshot analysis failed: {err}")),
+                }
+                Task::none()
+            }
+
+            Message::PollRelicNow => {
+                if self.poll.reward_capture_in_flight {
+                    return Task::none();
+                }
+                self.poll.reward_capture_in_flight = true;
+                self.poll.reward_capture_started = Some(Instant::now());
+
+                let target = self.source_target();
+                Task::perform(
+                    async move { capture(&target).map(Arc::new).map_err(|e| e.to_string()) },
+                    Message::RelicCaptured,
                 )
             }
 
+            Message::RelicCaptured(res) => {
+                self.poll.reward_capture_in_flight = false;
+                if let Some(started) = self.poll.reward_capture_started.take() {
+                    self.poll.metrics.record_capture(started.elapsed());
+                }
+
+                match res {
+                    Ok(img) if self.poll.reward_analysis_in_flight => {
+                        self.poll.pending_reward_frame = Some(img);
+                        Task::none()
+                    }
+                    Ok(img) => self.analyze_relic_frame(img),
+                    Err(err) => {
+                        self.status = Some(format!("Relic capture failed: {err}"));
+                        Task::none()
+                    }
+                }
+            }
+
             Message::RelicPolled(res) => {
-                self.poll.reward_in_flight = false;
+                self.poll.reward_analysis_in_flight = false;
+                if let Some(started) = self.poll.reward_analysis_started.take() {
+                    self.poll.metrics.record_analysis(started.elapsed(), Instant::now());
+                }
 
                 match res {
-                    Ok(v) => {
+                    Ok(mut v) => {
+                        self.relic.slot_hint = v.slot_hint;
+                        v.rewards = self.relic.vote(v.rewards);
+                        self.emit_relic_events(&v);
+                        if let Some(index) = v.selected {
+                            self.relic.last_pick = Some((v.rewards.clone(), index));
+                        }
                         self.relic.rewards = Some(v.rewards);
                         self.relic.selected = v.selected;
                         self.relic.last_updated = Some(Instant::now());
@@ -384,6 +1114,72 @@ impl App {
                         self.status = Some(format!("Relic poll failed: {err}"));
                     }
                 }
+
+                match self.poll.pending_reward_frame.take() {
+                    Some(img) => self.analyze_relic_frame(img),
+                    None => Task::none(),
+                }
+            }
+
+            Message::RelicNamePolled(res) => {
+                // Optional context; a failed read just leaves the header line out.
+                match res {
+                    Ok(name) => self.relic.relic_name = name,
+                    Err(err) => tracing::debug!(error = %err, "relic name read failed"),
+                }
+                Task::none()
+            }
+
+            Message::MissionTypePolled(res) => {
+                self.poll.mission_type_in_flight = false;
+                match res {
+                    // Nothing read (menus, hidden HUD) keeps the last known type.
+                    Ok(Some(mission_type)) if self.relic.mission_type != Some(mission_type) => {
+                        tracing::info!(%mission_type, "mission type");
+                        self.relic.mission_type = Some(mission_type);
+                    }
+                    Ok(_) => {}
+                    Err(err) => tracing::debug!(error = %err, "mission type read failed"),
+                }
+                Task::none()
+            }
+
+            Message::PollMissionNow => {
+                if self.poll.mission_in_flight {
+                    return Task::none();
+                }
+                self.poll.mission_in_flight = true;
+
+                let target = self.source_target();
+                let ie = self.ie.clone();
+
+                Task::perform(
+                    async move {
+                        let img = capture(&target).map_err(|e| e.to_string())?;
+                        let rewards = ie
+                            .lock()
+                            .map_err(|_| "IE mutex poisoned".to_string())?
+                            .missionreward_get_rewards(&img);
+                        Ok(rewards)
+                    },
+                    Message::MissionPolled,
+                )
+            }
+
+            Message::MissionPolled(res) => {
+                self.poll.mission_in_flight = false;
+
+                match res {
+                    Ok(v) => {
+                        // An empty read (e.g. the list still fading in) is retried on the next poll.
+                        self.mission.summary_read = !v.rewards.is_empty();
+                        self.mission.rewards = Some(v);
+                        self.mission.last_updated = Some(Instant::now());
+                    }
+                    Err(err) => {
+                        self.status = Some(format!("Mission poll failed: {err}"));
+                    }
+                }
                 Task::none()
             }
 
@@ -391,10 +1187,77 @@ impl App {
                 self.relic.valued_forma = v;
                 Task::none()
             }
+
+            Message::OpenMarket(slug) => {
+                let url = format!("https://warframe.market/items/{slug}");
+                if let Err(err) = open_url(&url) {
+                    self.status = Some(format!("Failed to open {url}: {err}"));
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Enter (or extend) reward mode for a short window to auto-refresh rewards.
+    fn enter_reward_mode(&mut self) -> Task<Message> {
+        let entering = self.poll.reward_mode_until.is_none();
+        let now = Instant::now();
+        self.poll.reward_mode_until = Some(now + self.config.reward_grace());
+        self.poll.next_reward_poll = now;
+
+        if !entering {
+            return Task::none();
+        }
+
+        self.relic.history.clear();
+        let recorded = self.finish_reward_screen();
+        if let Some(key) = self.relic.pending_pick.take() {
+            *self.relic.picked.entry(key).or_default() += 1;
+        }
+        self.relic.relic_name = None;
+        self.relic.slot_hint = None;
+
+        let target = self.source_target();
+        let ie = self.ie.clone();
+        let relic_name = Task::perform(
+            async move {
+                let img = capture(&target).map_err(|e| e.to_string())?;
+                let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                Ok(guard.util_relic_name_text(&img))
+            },
+            Message::RelicNamePolled,
+        );
+        Task::batch([recorded, relic_name])
+    }
+
+    /// Capture target of the reward and mission captures: the client the
+    /// header was read from, or the configured window.
+    fn source_target(&self) -> CaptureTarget {
+        let target = self.config.capture_target();
+        match &self.poll.source_window {
+            Some(window) if self.config.multi_client => target.for_window(window.id),
+            _ => target,
         }
     }
 
     fn on_tick(&mut self, now: Instant) -> Task<Message> {
+        // 0) Optional EE.log trigger, ahead of the party header poll.
+        if let Some(tail) = &mut self.log_tail {
+            match tail.poll() {
+                Ok(lines) if lines.iter().any(|line| crate::logwatcher::is_reward_trigger(line)) => {
+                    if self.poll.reward_mode_until.is_none() {
+                        tracing::info!("EE.log reported the reward screen");
+                        return self.enter_reward_mode();
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::warn!(error = %err, "EE.log read failed; log trigger disabled");
+                    self.log_tail = None;
+                }
+            }
+        }
+
         // 1) Lightweight periodic party header poll.
         let delay = Duration::from_secs_f32(self.config.poll_delay_s.max(0.1));
         if !self.poll.party_in_flight && now.duration_since(self.poll.last_party_poll) >= delay {
@@ -402,15 +1265,37 @@ impl App {
             return self.update(Message::PollPartyHeaderNow);
         }
 
-        // 2) While in reward-mode, refresh rewards at a moderate interval.
+        // 2) Outside of reward mode, occasionally read the mission type if enabled (best effort).
+        if self.config.read_mission_type
+            && self.poll.reward_mode_until.is_none()
+            && !self.poll.mission_type_in_flight
+            && now >= self.poll.next_mission_type_poll
+        {
+            self.poll.next_mission_type_poll = now + MISSION_TYPE_POLL;
+            self.poll.mission_type_in_flight = true;
+
+            let target = self.source_target();
+            let ie = self.ie.clone();
+            return Task::perform(
+                async move {
+                    let img = capture(&target).map_err(|e| e.to_string())?;
+                    let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                    Ok(guard.util_mission_type(&img))
+                },
+                Message::MissionTypePolled,
+            );
+        }
+
+        // 3) While in reward-mode, refresh rewards at a moderate interval.
         if let Some(until) = self.poll.reward_mode_until {
-            if now <= until && !self.poll.reward_in_flight && now >= self.poll.next_reward_poll {
-                self.poll.next_reward_poll = now + Duration::from_millis(350);
+            if now <= until && !self.poll.reward_capture_in_flight && now >= self.poll.next_reward_poll {
+                self.poll.next_reward_poll = now + self.config.auto_check_interval();
                 return self.update(Message::PollRelicNow);
             }
 
             if now > until {
                 self.poll.reward_mode_until = None;
+                return self.finish_reward_screen();
             }
         }
 
@@ -421,11 +1306,15 @@ impl App {
         let tabs = Row::new()
             .spacing(10)
             .push(tab_button(self.tab, Tab::RelicRewards))
+            .push(tab_button(self.tab, Tab::MissionRewards))
+            .push(tab_button(self.tab, Tab::PriceCheck))
             .push(tab_button(self.tab, Tab::Settings))
             .push(tab_button(self.tab, Tab::Debug));
 
         let content = match self.tab {
             Tab::RelicRewards => self.view_relic(),
+            Tab::MissionRewards => self.view_mission(),
+            Tab::PriceCheck => self.view_price_check(),
             Tab::Settings => self.view_settings(),
             Tab::Debug => self.view_debug(),
         };
@@ -444,6 +1333,25 @@ impl App {
         root.into()
     }
 
+    /// Rebuild the OCR engine from the current config; the engine keeps the old
+    /// one if loading fails.
+    fn reload_ocr(&self) -> Task<Message> {
+        let ie = self.ie.clone();
+        let lang = self.config.client_language;
+        let min_confidence = self.config.min_confidence;
+        Task::perform(
+            async move {
+                let (detection, recognition, charsset) = resolve_ocr_assets(lang);
+                let mut guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                guard
+                    .set_ocr_assets(detection, recognition, charsset, min_confidence)
+                    .map_err(|e| format!("{e:#}"))?;
+                Ok(guard.ocr_asset_source().cloned())
+            },
+            Message::OcrAssetsLoaded,
+        )
+    }
+
     fn view_settings(&self) -> Element<Message> {
         // Ensure both branches return the same type.
         // We keep the internal widget types flexible by converting to `Element`.
@@ -477,10 +1385,93 @@ impl App {
             .on_input(Message::PollDelayChanged)
             .width(Length::Fixed(160.0));
 
+        let reward_grace = TextInput::new("reward screen grace (ms)", &self.reward_grace_input)
+            .on_input(Message::RewardGraceChanged)
+            .width(Length::Fixed(180.0));
+
+        let auto_check = TextInput::new("reward check interval (ms)", &self.auto_check_input)
+            .on_input(Message::AutoCheckChanged)
+            .width(Length::Fixed(180.0));
+
         let max_h = TextInput::new("max capture height (blank=off)", &self.max_capture_height_input)
             .on_input(Message::MaxCaptureHeightChanged)
             .width(Length::Fixed(220.0));
 
+        let forma_plat = TextInput::new("forma plat value (blank=default)", &self.forma_plat_input)
+            .on_input(Message::FormaPlatChanged)
+            .width(Length::Fixed(220.0));
+
+        let min_plat = TextInput::new("dim rewards below plat (blank=off)", &self.min_plat_input)
+            .on_input(Message::MinPlatChanged)
+            .width(Length::Fixed(220.0));
+
+        let party_header_roi = TextInput::new(
+            "party header region x, y, w, h as fractions (blank=auto)",
+            &self.party_header_roi_input,
+        )
+        .on_input(Message::PartyHeaderRoiChanged)
+        .width(Length::Fill);
+
+        let webhook_url = TextInput::new("webhook URL on reward selection (blank=off)", &self.webhook_url_input)
+            .on_input(Message::WebhookUrlChanged)
+            .width(Length::Fill);
+
+        let language = Row::new()
+            .spacing(10)
+            .push(Text::new("Client language"))
+            .push(PickList::new(
+                data::Language::ALL,
+                Some(self.config.client_language),
+                Message::LanguagePicked,
+            ));
+
+        let min_confidence = Row::new()
+            .spacing(10)
+            .push(Text::new(format!("Min OCR confidence: {:.2}", self.config.min_confidence)))
+            .push(
+                slider(0.0..=1.0, self.config.min_confidence, Message::MinConfidenceChanged)
+                    .step(0.05)
+                    .on_release(Message::MinConfidenceReleased)
+                    .width(Length::Fixed(220.0)),
+            );
+
+        let ui_scale = Row::new()
+            .spacing(10)
+            .push(Text::new(format!("HUD scale: {:.0}%", self.config.ui_scale * 100.0)))
+            .push(
+                slider(0.5..=1.0, self.config.ui_scale, Message::UiScaleChanged)
+                    .step(0.05)
+                    .width(Length::Fixed(220.0)),
+            )
+            .push(Button::new(Text::new("Calibrate (reward screen)")).on_press(Message::CalibrateUiScale));
+
+        let blank_threshold = Row::new()
+            .spacing(10)
+            .push(Text::new(format!("Blank frame threshold: {}", self.config.blank_threshold)))
+            .push(
+                slider(0..=64, self.config.blank_threshold, Message::BlankThresholdChanged)
+                    .on_release(Message::BlankThresholdReleased)
+                    .width(Length::Fixed(220.0)),
+            );
+
+        let reward_list_style = Row::new()
+            .spacing(10)
+            .push(Text::new("Reward list style"))
+            .push(PickList::new(
+                RewardListStyle::ALL,
+                Some(self.config.reward_list_style),
+                Message::RewardListStylePicked,
+            ));
+
+        let reward_font_scale = Row::new()
+            .spacing(10)
+            .push(Text::new(format!("Reward list text: {:.0}%", self.config.reward_font_scale * 100.0)))
+            .push(
+                slider(crate::config::REWARD_FONT_SCALE, self.config.reward_font_scale, Message::RewardFontScaleChanged)
+                    .step(0.1)
+                    .width(Length::Fixed(220.0)),
+            );
+
         let theme = self.config.theme;
         let theme_text = Text::new(format!(
             "Theme:\n  primary:   ({}, {}, {})\n  secondary: ({}, {}, {})",
@@ -492,26 +1483,95 @@ impl App {
             theme.secondary.b
         ));
 
-        Column::new()
+        let mut col = Column::new()
             .spacing(12)
             .push(Text::new("Target Window"))
             .push(window_picker)
             .push(Text::new("Or set by app_name:"))
             .push(app_name)
-            .push(Row::new().spacing(10).push(poll_delay).push(max_h))
+            .push(
+                Checkbox::new(self.config.match_window_title)
+                    .label("Match app_name against window titles instead (substring)")
+                    .on_toggle(Message::MatchWindowTitleToggled),
+            )
+            .push(Row::new().spacing(10).push(poll_delay).push(max_h).push(forma_plat).push(min_plat))
+            .push(Row::new().spacing(10).push(reward_grace).push(auto_check))
+            .push(
+                Checkbox::new(self.config.monitor_capture_fallback)
+                    .label("Capture the monitor when the window capture is black (borderless/windowed)")
+                    .on_toggle(Message::MonitorFallbackToggled),
+            )
+            .push(
+                Checkbox::new(self.config.desktop_duplication)
+                    .label("Use desktop duplication when the window capture is black (exclusive fullscreen, Windows)")
+                    .on_toggle(Message::DesktopDuplicationToggled),
+            )
+            .push(
+                Checkbox::new(self.config.offline)
+                    .label("Offline mode: never use the network (save config and restart to apply)")
+                    .on_toggle(Message::OfflineToggled),
+            )
+            .push(
+                Checkbox::new(self.config.multi_client)
+                    .label("Watch every window with this app_name (several game clients)")
+                    .on_toggle(Message::MultiClientToggled),
+            )
+            .push(
+                Checkbox::new(self.config.read_mission_type)
+                    .label("Read the mission type from the HUD every few seconds")
+                    .on_toggle(Message::ReadMissionTypeToggled),
+            )
+            .push(
+                Checkbox::new(self.config.count_duplicates_once)
+                    .label("Count a reward shown in several slots once in the totals")
+                    .on_toggle(Message::CountDuplicatesOnceToggled),
+            )
+            .push(
+                Checkbox::new(self.config.ee_log_trigger)
+                    .label("Also trigger on EE.log reward lines (save config and restart to apply)")
+                    .on_toggle(Message::EeLogTriggerToggled),
+            )
+            .push(language)
+            .push(min_confidence)
+            .push(ui_scale)
+            .push(blank_threshold)
+            .push(reward_font_scale)
+            .push(reward_list_style)
+            .push(Button::new(Text::new("Reset reward list settings")).on_press(Message::ResetRewardListSettings))
+            .push(party_header_roi)
+            .push(webhook_url)
             .push(Row::new().spacing(10).push(Button::new(Text::new("Save Config")).on_press(Message::SaveConfig)))
-            .push(Row::new().spacing(10).push(Button::new(Text::new("Sample Theme (capture)")).on_press(Message::SampleTheme)))
-            .push(theme_text)
-            .into()
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(Button::new(Text::new("Sample Theme (capture)")).on_press(Message::SampleTheme))
+                    .push(Button::new(Text::new("Check Theme (reward screen)")).on_press(Message::ValidateTheme)),
+            )
+            .push(Text::new("Open the in-game options and hover the first list entry before sampling."))
+            .push(theme_text);
+
+        if let Some(warning) = &self.theme_warning {
+            col = col.push(Text::new(warning).style(text::danger));
+        }
+
+        col.into()
     }
 
     fn view_relic(&self) -> Element<Message> {
+        if self.config.reward_list_style == RewardListStyle::Minimal {
+            return self.view_relic_minimal();
+        }
+
         let mut col = Column::new().spacing(10);
 
         col = col.push(
             Row::new()
                 .spacing(10)
                 .push(Button::new(Text::new("Poll Now")).on_press(Message::PollRelicNow))
+                .push(
+                    Button::new(Text::new("Copy Rewards"))
+                        .on_press_maybe(self.relic.rewards.is_some().then_some(Message::CopyRewards)),
+                )
                 .push(
                     // iced 0.14: Checkbox::new only takes the checked state.
                     // Set the label separately.
@@ -521,43 +1581,130 @@ impl App {
                 ),
         );
 
+        col = col.push(
+            Row::new()
+                .spacing(10)
+                .push(
+                    TextInput::new("…or analyze a screenshot file (PNG/JPEG path)", &self.screenshot_path_input)
+                        .on_input(Message::ScreenshotPathChanged)
+                        .on_submit(Message::AnalyzeScreenshot)
+                        .width(Length::Fill),
+                )
+                .push(Button::new(Text::new("Analyze")).on_press(Message::AnalyzeScreenshot)),
+        );
+
+        col = col.push(Text::new(format!(
+            "Session: {:.1}p / {} ducats",
+            self.relic.session_platinum, self.relic.session_ducats
+        )));
+
+        col = col.push(
+            Row::new()
+                .spacing(10)
+                .push(Text::new(format!(
+                    "Lifetime: {:.1}p / {} ducats / {} relics",
+                    self.stats.total_plat, self.stats.total_ducats, self.stats.relics_opened
+                )))
+                .push(Button::new(Text::new("Reset")).on_press(Message::ResetStats)),
+        );
+
         if let Some(rewards) = &self.relic.rewards {
-            col = col.push(Text::new(format!("Timer: {}s", rewards.timer)));
+            let mission = self.relic.mission_type.map(|m| format!(" ({m})")).unwrap_or_default();
+            if let Some(relic) = &self.relic.relic_name {
+                // Whether the relic being opened is in the current drop tables.
+                let relic_id = self
+                    .data
+                    .as_ref()
+                    .filter(|d| d.has_droptable())
+                    .and_then(|d| Some((d, d.relic_id(relic)?)));
+                let source = match relic_id {
+                    Some((data, id)) if data.is_relic_active(id) => "  [FARMABLE NOW]",
+                    Some(_) => "  [VAULTED RELIC]",
+                    None => "",
+                };
+                col = col.push(
+                    Text::new(format!("{relic}{mission} — choose reward{source}")).size(self.config.reward_font_size(20.0)),
+                );
+            } else if let Some(mission_type) = self.relic.mission_type {
+                col = col.push(Text::new(format!("Mission: {mission_type}")).size(self.config.reward_font_size(16.0)));
+            }
+            if let Some(window) = self.poll.source_window.as_ref().filter(|_| self.config.multi_client) {
+                col = col.push(Text::new(format!("Client: {window}")).style(text::secondary));
+            }
+            col = col.push(Text::new(format!("Timer: {}s", rewards.timer)).size(self.config.reward_font_size(16.0)));
 
             let mut list = Column::new().spacing(6);
 
             for (i, r) in rewards.rewards.iter().enumerate() {
                 let selected = self.relic.selected == Some(i);
-                let name = if r.name.is_empty() { "<unknown>".to_string() } else { r.name.clone() };
-
-                let (ducats, vaulted, is_relic_item) = self.lookup_item(&name);
-
-                let ducats = if !self.relic.valued_forma && name.to_lowercase().contains("forma") {
-                    0
-                } else {
-                    ducats.unwrap_or(0)
-                };
+                let RewardRow {
+                    info,
+                    name,
+                    platinum,
+                    ducats,
+                    priced,
+                    owned_before,
+                    picked,
+                } = self.reward_row(r);
+                let low_value = priced && self.config.min_plat > 0.0 && platinum < self.config.min_plat;
 
+                let platinum = if priced { format!("{platinum:.1}") } else { "-".to_string() };
                 let mut line = format!(
-                    "#{:02}  owned:{:<2}  ducats:{:<3}  {}",
+                    "#{:02}  owned:{:<2}  picked:{:<2}  plat:{:<5}  ducats:{:<3}  {}",
                     i + 1,
-                    r.owned,
+                    owned_before,
+                    picked,
+                    platinum,
                     ducats,
                     name
                 );
 
-                if vaulted.unwrap_or(false) {
+                if info.unknown {
+                    line.push_str("  [UNKNOWN]");
+                }
+                if let Some(category) = info.category {
+                    line.push_str(&format!("  ({category})"));
+                }
+                if r.duplicates > 1 {
+                    line.push_str(&format!("  [x{} slots]", r.duplicates));
+                }
+                if info.matched_name.is_some() && !priced {
+                    line.push_str("  [NO MARKET VALUE]");
+                }
+                if low_value {
+                    line.push_str("  [LOW]");
+                }
+                if info.vaulted.unwrap_or(false) {
                     line.push_str("  [VAULTED]");
+                } else if !info.active_relics.is_empty() {
+                    line.push_str("  [FARMABLE]");
                 }
-                if is_relic_item.unwrap_or(false) {
+                if info.relic.unwrap_or(false) {
                     line.push_str("  [RELIC]");
                 }
+                if !info.active_relics.is_empty() {
+                    // A handful is enough to answer "can I farm this right now?".
+                    let mut relics = info.active_relics.clone();
+                    relics.sort();
+                    let more = relics.len().saturating_sub(3);
+                    relics.truncate(3);
+                    line.push_str(&format!("  from: {}", relics.join(", ")));
+                    if more > 0 {
+                        line.push_str(&format!(" +{more}"));
+                    }
+                }
 
-				let text = Text::new(line);
+				// Dimmed rather than hidden, so the list still lines up with the in-game slots.
+				let line = Text::new(line).size(self.config.reward_font_size(16.0)).width(Length::Fill);
+				let line = if low_value { line.style(text::secondary) } else { line };
+				let mut row = Row::new().spacing(10).push(line);
+				if let Some(slug) = info.market_slug {
+					row = row.push(Button::new(Text::new("Market")).on_press(Message::OpenMarket(slug)));
+				}
 
 				// Highlight the currently-selected reward row.
 				let style = if selected { container::primary } else { container::transparent };
-				list = list.push(Container::new(text).padding(6).style(style));
+				list = list.push(Container::new(row).padding(6).style(style));
             }
 
             col = col.push(Scrollable::new(list).height(Length::Fill));
@@ -570,6 +1717,81 @@ impl App {
         col.into()
     }
 
+    /// [`RewardListStyle::Minimal`]: one plain line per reward, no controls,
+    /// frames, tags or totals.
+    fn view_relic_minimal(&self) -> Element<Message> {
+        let mut col = Column::new().spacing(4);
+        for r in self.relic.rewards.iter().flat_map(|rewards| &rewards.rewards) {
+            let row = self.reward_row(r);
+            let line = match row.priced {
+                true => format!("{} — {:.0}p / {}d", row.name, row.platinum, row.ducats),
+                false => format!("{} — {}d", row.name, row.ducats),
+            };
+            col = col.push(Text::new(line).size(self.config.reward_font_size(16.0)));
+        }
+        col.into()
+    }
+
+    fn view_price_check(&self) -> Element<Message> {
+        let mut col = Column::new().spacing(10).push(
+            TextInput::new("search any tradeable item…", &self.price_search_input)
+                .on_input(Message::PriceSearchChanged)
+                .width(Length::Fill),
+        );
+
+        let Some(data) = &self.data else {
+            return col.push(Text::new("Item data unavailable (offline?).")).into();
+        };
+
+        let mut list = Column::new().spacing(6);
+        for (name, id) in data.search(&self.price_search_input, 30) {
+            let platinum = data
+                .platinum_values
+                .get(&id)
+                .map_or_else(|| "?".to_string(), |p| format!("{p:.1}"));
+            let ducats = data.ducat_values.get(&id).copied().unwrap_or(0);
+            let mut line = format!("plat:{platinum:<6}  ducats:{ducats:<3}  {name}");
+            if data.vaulted_items.contains(&id) {
+                line.push_str("  [VAULTED]");
+            }
+
+            let mut row = Row::new().spacing(10).push(Text::new(line).width(Length::Fill));
+            if let Some(slug) = data.market_slug(id) {
+                row = row.push(Button::new(Text::new("Market")).on_press(Message::OpenMarket(slug)));
+            }
+            list = list.push(row);
+        }
+
+        col = col.push(Scrollable::new(list).height(Length::Fill));
+        col.into()
+    }
+
+    fn view_mission(&self) -> Element<Message> {
+        let mut col = Column::new()
+            .spacing(10)
+            .push(Button::new(Text::new("Poll Now")).on_press(Message::PollMissionNow));
+
+        match &self.mission.rewards {
+            Some(rewards) if !rewards.rewards.is_empty() => {
+                if let Some(window) = self.poll.source_window.as_ref().filter(|_| self.config.multi_client) {
+                    col = col.push(Text::new(format!("Client: {window}")).style(text::secondary));
+                }
+                let mut list = Column::new().spacing(6);
+                for r in &rewards.rewards {
+                    list = list.push(Text::new(format!("{:>5} x  {}", r.count, r.name)));
+                }
+                col = col.push(Scrollable::new(list).height(Length::Fill));
+            }
+            _ => {
+                col = col
+                    .push(Text::new("No mission rewards yet."))
+                    .push(Text::new("Tip: Rewards are read automatically when the mission summary screen is open."));
+            }
+        }
+
+        col.into()
+    }
+
     fn view_debug(&self) -> Element<Message> {
         let last = self
             .debug
@@ -577,33 +1799,262 @@ impl App {
             .clone()
             .unwrap_or_else(|| "<none>".to_string());
 
+        let ocr_source = match &self.debug.ocr_asset_source {
+            Some(source) => source.to_string(),
+            None => "<OCR unavailable>".to_string(),
+        };
+
+        let slots = match &self.debug.reward_slots {
+            None => "<not run>".to_string(),
+            Some(slots) if slots.is_empty() => "<none found>".to_string(),
+            Some(slots) => slots
+                .iter()
+                .map(|r| format!("({}, {}) {}x{}", r.x, r.y, r.w, r.h))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+
+        let metrics = &self.poll.metrics;
+        let ms = |d: Option<Duration>| d.map_or_else(|| "-".to_string(), |d| format!("{} ms", d.as_millis()));
+        let metrics = format!(
+            "  capture: {}\n  analysis: {}\n  effective: {}",
+            ms(metrics.capture),
+            ms(metrics.analysis),
+            metrics.fps().map_or_else(|| "-".to_string(), |fps| format!("{fps:.1} fps")),
+        );
+
         Column::new()
             .spacing(12)
             .push(Button::new(Text::new("Capture + OCR Party Header")).on_press(Message::PollPartyHeaderNow))
             .push(Text::new(format!("Last party header: {last}")))
+            .push(Button::new(Text::new("Capture + Detect Reward Slots")).on_press(Message::DetectSlotsNow))
+            .push(Text::new(format!("Reward slots:\n{slots}")))
+            .push(Button::new(Text::new("Run self-test (bundled sample)")).on_press(Message::RunSelfTest))
+            .push(Text::new(self.debug.self_test.as_deref().unwrap_or("<not run>")))
+            .push(Text::new(format!("OCR model: {ocr_source}")))
+            .push(Text::new(format!("Relic poll timing:\n{metrics}")))
             .into()
     }
 
-    fn lookup_item(&self, name: &str) -> (Option<u32>, Option<bool>, Option<bool>) {
-        let Some(data) = &self.data else {
-            return (None, None, None);
+    /// Run reward detection on a captured frame (the analysis stage of a relic poll).
+    fn analyze_relic_frame(&mut self, img: Arc<ie::OwnedImage>) -> Task<Message> {
+        self.poll.reward_analysis_in_flight = true;
+        self.poll.reward_analysis_started = Some(Instant::now());
+
+        let ui_scale = self.config.ui_scale;
+        let hint = self.relic.slot_hint;
+        let ie = self.ie.clone();
+        Task::perform(
+            async move {
+                let guard = ie.lock().map_err(|_| "IE mutex poisoned".to_string())?;
+                let analysis = guard.relicreward_analyze_hinted(&img, ui_scale, hint);
+                Ok(RelicPollResult {
+                    rewards: analysis.rewards,
+                    selected: analysis.selected,
+                    slot_hint: ie::screen::relicreward::SlotHint::from_slots(&analysis.rects),
+                })
+            },
+            Message::RelicPolled,
+        )
+    }
+
+    /// Platinum value of `count` forma blueprints, honoring the config override.
+    fn forma_platinum(&self, count: u32) -> f32 {
+        match self.config.forma_plat_override {
+            Some(value) => value * count as f32,
+            None => data::forma_plat_value(count as f32),
+        }
+    }
+
+    /// Display name and value of a detected reward.
+    fn reward_row(&self, r: &ie::screen::relicreward::RelicReward) -> RewardRow {
+        let info = self.lookup_item(&r.name);
+        let name = match &info.matched_name {
+            Some(matched) if info.fuzzy => format!("{matched} (read \"{}\")", r.name),
+            Some(matched) => matched.clone(),
+            None if !r.name.is_empty() => r.name.clone(),
+            None => "<unreadable>".to_string(),
         };
 
-        // Best-effort mapping: if anything is missing, return partial info.
-        // `data::Data::find_item_name` expects a `(Language, &str)` tuple (or similar)
-        // so it can apply language-specific matching.
-        let canonical = data.find_item_name((data::Language::English, name));
+        let is_forma = self
+            .data
+            .as_ref()
+            .zip(info.matched_name.as_deref())
+            .is_some_and(|(d, matched)| d.is_forma_blueprint(matched));
+        let stack = trusted_stack(is_forma, r.stack);
+        let ducats = if !self.relic.valued_forma && is_forma {
+            0
+        } else {
+            info.ducats.unwrap_or(0) * stack
+        };
+        let platinum = self.reward_platinum(&info, is_forma, stack);
+        let priced = is_forma || info.platinum.is_some();
+
+        let key = pick_key(&info, &r.name);
+        let earlier = self.relic.picked.get(&key).copied().unwrap_or(0);
+        let pending = self.relic.pending_pick.as_ref() == Some(&key);
+
+        RewardRow {
+            info,
+            name,
+            platinum,
+            ducats,
+            priced,
+            owned_before: r.owned.saturating_sub(earlier),
+            picked: earlier + pending as u32,
+        }
+    }
+
+    /// Current rewards as plain text, one line per slot plus the total
+    /// (for pasting into chat or a bug report).
+    fn rewards_text(&self) -> Option<String> {
+        let rewards = self.relic.rewards.as_ref()?;
+
+        let mut out = String::new();
+        let (mut total_platinum, mut total_ducats) = (0.0, 0);
+        for (i, r) in rewards.rewards.iter().enumerate() {
+            let row = self.reward_row(r);
+            // With duplicates counted once, only the first slot showing a reward adds to the total.
+            let counted = !self.config.count_duplicates_once
+                || r.duplicates <= 1
+                || !rewards.rewards[..i].iter().any(|prev| prev.name.trim().eq_ignore_ascii_case(r.name.trim()));
+            if counted {
+                total_platinum += row.platinum;
+                total_ducats += row.ducats;
+            }
 
-        let id = data.id_manager.get_id_from_en(canonical);
-        let Some(id) = id else {
-            return (None, None, None);
+            match row.priced {
+                true => out.push_str(&format!("#{} {} — {:.0}p / {}d", i + 1, row.name, row.platinum, row.ducats)),
+                false => out.push_str(&format!("#{} {} — no market value", i + 1, row.name)),
+            }
+            if row.info.vaulted.unwrap_or(false) {
+                out.push_str(" [VAULTED]");
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!("Total: {total_platinum:.0}p / {total_ducats}d"));
+        Some(out)
+    }
+
+    /// Platinum value of a reward slot holding `stack` of the item.
+    fn reward_platinum(&self, info: &ItemInfo, is_forma: bool, stack: u32) -> f32 {
+        let stack = stack.max(1);
+        match is_forma {
+            true if !self.relic.valued_forma => 0.0,
+            true => self.forma_platinum(stack),
+            false => info.platinum.unwrap_or(0.0) * stack as f32,
+        }
+    }
+
+    /// Record the final pick of the reward screen that just closed, if any.
+    fn finish_reward_screen(&mut self) -> Task<Message> {
+        match self.relic.last_pick.take() {
+            Some((rewards, index)) => self.record_selection(&rewards, index),
+            None => Task::none(),
+        }
+    }
+
+    /// Add a selected reward to the session totals and notify the webhook.
+    fn record_selection(&mut self, rewards: &ie::screen::relicreward::Rewards, index: usize) -> Task<Message> {
+        let Some(reward) = rewards.rewards.get(index) else {
+            return Task::none();
         };
 
-        let ducats = data.ducat_values.get(&id).copied();
-        let vaulted = Some(data.vaulted_items.contains(&id));
-        let relic = Some(data.relic_items.contains(&id));
+        let info = self.lookup_item(&reward.name);
+        let is_forma = self
+            .data
+            .as_ref()
+            .zip(info.matched_name.as_deref())
+            .is_some_and(|(d, matched)| d.is_forma_blueprint(matched));
+        let stack = trusted_stack(is_forma, reward.stack);
+        let platinum = self.reward_platinum(&info, is_forma, stack);
+        let ducats = info.ducats.unwrap_or(0) * stack;
+
+        self.relic.session_platinum += platinum;
+        self.relic.session_ducats += ducats;
+        self.relic.pending_pick = Some(pick_key(&info, &reward.name));
+        self.stats.record(platinum, ducats);
+
+        if let Some(url) = self.config.webhook_url.as_ref().filter(|_| !data::is_offline()) {
+            crate::webhook::post_selection(
+                url.clone(),
+                crate::webhook::SelectionPayload {
+                    name: reward.name.clone(),
+                    platinum,
+                    ducats,
+                    session_platinum: self.relic.session_platinum,
+                    session_ducats: self.relic.session_ducats,
+                },
+            );
+        }
 
-        (ducats, vaulted, relic)
+        self.save_stats()
+    }
+
+    /// Persist the lifetime stats off the UI thread, reporting failures in the status line.
+    fn save_stats(&self) -> Task<Message> {
+        let stats = self.stats.clone();
+        Task::perform(async move { stats.save().map_err(|e| e.to_string()) }, Message::StatsSaved)
+    }
+
+    /// Publish reward/selection changes to the event sink (if enabled).
+    fn emit_relic_events(&self, poll: &RelicPollResult) {
+        let Some(events) = &self.events else {
+            return;
+        };
+
+        let names = |r: &ie::screen::relicreward::Rewards| r.rewards.iter().map(|v| v.name.clone()).collect::<Vec<_>>();
+        let changed = self.relic.rewards.as_ref().is_none_or(|prev| names(prev) != names(&poll.rewards));
+        if changed && !poll.rewards.rewards.is_empty() {
+            events.emit(&DetectionEvent::Rewards {
+                rewards: &poll.rewards,
+            });
+        }
+
+        if poll.selected != self.relic.selected {
+            if let Some(index) = poll.selected {
+                let name = poll.rewards.rewards.get(index).map_or("", |r| r.name.as_str());
+                events.emit(&DetectionEvent::Selected { index, name });
+            }
+        }
+    }
+
+    fn lookup_item(&self, name: &str) -> ItemInfo {
+        self.data
+            .as_ref()
+            .map_or_else(ItemInfo::default, |data| lookup_item(data, name))
+    }
+}
+
+/// Market/relic info for a detected reward name.
+///
+/// Best-effort mapping: if anything is missing, return partial info.
+/// Relic reward slots can only hold relic drops, so match against those first.
+/// Names are in the language `data` was populated with.
+fn lookup_item(data: &data::Data, name: &str) -> ItemInfo {
+    let matched = data.match_relic_reward(name);
+    let (Some(id), Some(canonical)) = (matched.id(), matched.name()) else {
+        return ItemInfo {
+            unknown: true,
+            ..ItemInfo::default()
+        };
+    };
+
+    ItemInfo {
+        matched_name: Some(canonical.to_string()),
+        fuzzy: matched.is_fuzzy(),
+        market_slug: data.market_slug(id),
+        platinum: data.platinum_values.get(&id).copied(),
+        ducats: data.ducat_values.get(&id).copied(),
+        vaulted: Some(data.vaulted_items.contains(&id)),
+        relic: Some(data.relic_items.contains(&id)),
+        category: data.item_categories.get(&id).copied(),
+        active_relics: data
+            .active_relics_with(id)
+            .filter_map(|relic| data.id_manager.get_locale_from_id(data.lang, relic))
+            .map(str::to_string)
+            .collect(),
+        unknown: false,
     }
 }
 
@@ -616,6 +2067,45 @@ fn tab_button(current: Tab, tab: Tab) -> Element<'static, Message> {
 		.into()
 }
 
+/// Parse `"x, y, w, h"` fractions (0..1); anything else means "auto".
+fn parse_relative_rect(s: &str) -> Option<ie::RelativeRect> {
+    let v = s
+        .split(',')
+        .map(|part| part.trim().parse::<f32>().ok().filter(|v| (0.0..=1.0).contains(v)))
+        .collect::<Option<Vec<_>>>()?;
+    match v[..] {
+        [x, y, w, h] if w > 0.0 && h > 0.0 => Some(ie::RelativeRect::new(x, y, w, h)),
+        _ => None,
+    }
+}
+
+/// Open a URL in the default browser.
+fn open_url(url: &str) -> anyhow::Result<()> {
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = std::process::Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    };
+    #[cfg(target_os = "macos")]
+    let mut cmd = std::process::Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut cmd = std::process::Command::new("xdg-open");
+
+    cmd.arg(url).spawn()?;
+    Ok(())
+}
+
+/// Resolve the OCR model files (detection, recognition, charset) for a client language.
+fn resolve_ocr_assets(lang: data::Language) -> (PathBuf, PathBuf, PathBuf) {
+    let ocr_code = lang.ocr_code();
+    (
+        resolve_model_path("ocr/detection.mnn"),
+        resolve_model_path(&format!("ocr/{ocr_code}_recognition.mnn")),
+        resolve_model_path(&format!("ocr/{ocr_code}_charset.txt")),
+    )
+}
+
 fn resolve_model_path(rel: &str) -> PathBuf {
     let rel = PathBuf::from(rel);
 
@@ -637,3 +2127,33 @@ fn resolve_model_path(rel: &str) -> PathBuf {
 
     rel
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_unknown_item() {
+        let data = data::Data::with_ids(data::Language::English, data::IdManager::new());
+
+        for name in ["Brand New Prime Blueprint", "", "|||"] {
+            let info = lookup_item(&data, name);
+            assert!(info.unknown);
+            assert_eq!(info.matched_name, None);
+            assert_eq!(info.platinum.unwrap_or(0.0), 0.0);
+            assert_eq!(info.ducats.unwrap_or(0), 0);
+        }
+    }
+
+    #[test]
+    fn lookup_known_item() {
+        let mut ids = data::IdManager::new();
+        ids.add_locale_en("Forma Blueprint", "/Lotus/StoreItems/Types/Recipes/Components/FormaBlueprint");
+        let data = data::Data::with_ids(data::Language::English, ids);
+
+        let info = lookup_item(&data, "Forma Blueprint");
+        assert!(!info.unknown);
+        assert_eq!(info.matched_name.as_deref(), Some("Forma Blueprint"));
+        assert!(!info.fuzzy);
+    }
+}
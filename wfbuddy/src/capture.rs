@@ -6,6 +6,9 @@ use anyhow::{anyhow, Context, Result};
 // `EncodableLayout` trait.
 use xcap::image::EncodableLayout;
 
+#[cfg(windows)]
+mod dxgi;
+
 /// Basic window descriptor for UI selection.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WindowInfo {
@@ -14,59 +17,439 @@ pub struct WindowInfo {
     pub title: String,
 }
 
+impl WindowInfo {
+    /// Whether this is the window targeted by `name`.
+    ///
+    /// Compares `app_name` exactly. With `by_title`, or for windows without an
+    /// app name (Wayland and some other compositors only report a title),
+    /// `name` is looked for in the title instead (case-insensitive substring).
+    pub fn matches(&self, name: &str, by_title: bool) -> bool {
+        if by_title || self.app_name.is_empty() {
+            !name.is_empty() && self.title.to_lowercase().contains(&name.to_lowercase())
+        } else {
+            self.app_name == name
+        }
+    }
+
+    /// Name to target this window by: its app name, or its title if it has none.
+    pub fn target_name(&self) -> &str {
+        if self.app_name.is_empty() { &self.title } else { &self.app_name }
+    }
+}
+
 impl std::fmt::Display for WindowInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Keep it short but informative.
         if self.title.is_empty() {
             write!(f, "{} (id:{})", self.app_name, self.id)
+        } else if self.app_name.is_empty() {
+            write!(f, "{} (id:{})", self.title, self.id)
         } else {
             write!(f, "{} — {} (id:{})", self.app_name, self.title, self.id)
         }
     }
 }
 
-pub fn list_windows() -> Result<Vec<WindowInfo>> {
-    let windows = xcap::Window::all().context("xcap::Window::all")?;
+/// Position and size of a window in desktop coordinates (logical pixels).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowBounds {
+    /// Overlapping area of both bounds, `None` if they don't overlap.
+    pub fn intersect(&self, other: &WindowBounds) -> Option<WindowBounds> {
+        let x1 = self.x.max(other.x);
+        let y1 = self.y.max(other.y);
+        let x2 = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let y2 = (self.y + self.height as i32).min(other.y + other.height as i32);
+        (x2 > x1 && y2 > y1).then(|| WindowBounds {
+            x: x1,
+            y: y1,
+            width: (x2 - x1) as u32,
+            height: (y2 - y1) as u32,
+        })
+    }
+}
 
-    let mut out = Vec::with_capacity(windows.len());
-    for w in windows {
-        // Some platforms may return empty strings; keep them but UI can filter.
-        out.push(WindowInfo {
-            id: w.id(),
-			app_name: w.app_name().to_string(),
-			title: w.title().to_string(),
-        });
+/// Source of window listings and captures.
+///
+/// [`XcapBackend`] is the default; other sources (remote capture, virtual
+/// cameras, recorded frames) only need to implement this.
+pub trait CaptureBackend {
+    /// All windows that can be captured.
+    fn list_windows(&self) -> Result<Vec<WindowInfo>>;
+
+    /// Capture the window with the given [`WindowInfo::id`].
+    fn capture(&self, id: u32) -> Result<ie::OwnedImage>;
+
+    /// Current bounds of the window with the given [`WindowInfo::id`].
+    fn window_bounds(&self, id: u32) -> Result<WindowBounds>;
+}
+
+/// Capture through `xcap`.
+///
+/// Windows are enumerated once and the handles kept for the backend's
+/// lifetime, so create one per capture (or per poll of several windows).
+#[derive(Default)]
+pub struct XcapBackend {
+    /// Capture the window's monitor instead when the window capture is black.
+    pub monitor_fallback: bool,
+    /// Capture the window from a desktop duplication of its output when the
+    /// window capture is black (Windows only, see [`XcapBackend::capture`]).
+    pub desktop_duplication: bool,
+    /// Threshold a window capture counts as black below (see [`ie::OwnedImage::is_blank`]).
+    pub blank_threshold: u8,
+    windows: std::cell::RefCell<Vec<xcap::Window>>,
+}
+
+impl XcapBackend {
+    /// Backend with the target's capture settings.
+    pub fn for_target(target: &CaptureTarget) -> Self {
+        Self {
+            monitor_fallback: target.monitor_fallback,
+            desktop_duplication: target.desktop_duplication,
+            blank_threshold: target.blank_threshold,
+            ..Self::default()
+        }
+    }
+
+    /// Enumerate the windows, replacing the ones from an earlier enumeration.
+    fn enumerate(&self) -> Result<()> {
+        *self.windows.borrow_mut() = xcap::Window::all().context("xcap::Window::all")?;
+        Ok(())
     }
 
+    /// Run `f` on the window with the given id, enumerating the windows only
+    /// if they weren't yet or the id is unknown.
+    fn with_window<T>(&self, id: u32, f: impl FnOnce(&xcap::Window) -> Result<T>) -> Result<T> {
+        if !self.windows.borrow().iter().any(|w| w.id() == id) {
+            self.enumerate()?;
+        }
+        let windows = self.windows.borrow();
+        let window = windows
+            .iter()
+            .find(|w| w.id() == id)
+            .ok_or_else(|| anyhow!("window not found: id={id}"))?;
+        f(window)
+    }
+
+    /// Capture the monitor the window is on and crop it to the window bounds.
+    fn capture_window_monitor(&self, window: &xcap::Window) -> Result<ie::OwnedImage> {
+        let bounds = xcap_bounds(window);
+        let monitor = window.current_monitor();
+        let img = monitor.capture_image().context("xcap::Monitor::capture_image")?;
+        let full = ie::OwnedImage::from_rgba(img.width() as usize, img.as_bytes());
+
+        // Only the part of the window that is on this monitor can be captured;
+        // a window hanging off the left/top edge would otherwise shift the crop
+        // onto whatever is right/below of it.
+        let visible = bounds
+            .intersect(&WindowBounds {
+                x: monitor.x(),
+                y: monitor.y(),
+                width: monitor.width(),
+                height: monitor.height(),
+            })
+            .ok_or_else(|| anyhow!("window is not on its monitor: {bounds:?}"))?;
+
+        // Position relative to the monitor origin (monitor captures are in
+        // physical pixels, so apply the monitor's scale factor).
+        let scale = monitor.scale_factor();
+        let x = ((visible.x - monitor.x()) as f32 * scale) as u32;
+        let y = ((visible.y - monitor.y()) as f32 * scale) as u32;
+        let w = (visible.width as f32 * scale) as u32;
+        let h = (visible.height as f32 * scale) as u32;
+
+        Ok(full.as_image().sub_image(x, y, w, h).to_owned_image())
+    }
+}
+
+impl CaptureBackend for XcapBackend {
+    fn list_windows(&self) -> Result<Vec<WindowInfo>> {
+        self.enumerate()?;
+        let windows = self.windows.borrow();
+
+        let mut out = Vec::with_capacity(windows.len());
+        for w in windows.iter() {
+            // Some platforms may return empty strings; keep them but UI can filter.
+            out.push(WindowInfo {
+                id: w.id(),
+                app_name: w.app_name().to_string(),
+                title: w.title().to_string(),
+            });
+        }
+        Ok(out)
+    }
+
+    /// Window capture can come back black, e.g. with hardware-accelerated
+    /// presentation on some Windows setups, and always in exclusive fullscreen.
+    /// Such frames are replaced by, in order of preference:
+    /// - with `desktop_duplication` (Windows only), a DXGI desktop duplication
+    ///   of the window's output cropped to the window, which also sees
+    ///   exclusive fullscreen games;
+    /// - with `monitor_fallback`, a capture of the window's monitor cropped to
+    ///   the window bounds, which is black in exclusive fullscreen as well.
+    fn capture(&self, id: u32) -> Result<ie::OwnedImage> {
+        self.with_window(id, |window| {
+            let img = window.capture_image().context("xcap::Window::capture_image")?;
+            let mut out = ie::OwnedImage::from_rgba(img.width() as usize, img.as_bytes());
+            if !out.is_blank(self.blank_threshold) {
+                return Ok(out);
+            }
+
+            #[cfg(windows)]
+            if self.desktop_duplication {
+                match dxgi::capture_window(id) {
+                    Ok(img) => return Ok(img),
+                    Err(err) => tracing::warn!(error = %err, "desktop duplication fallback failed"),
+                }
+            }
+
+            if self.monitor_fallback {
+                match self.capture_window_monitor(window) {
+                    Ok(img) => out = img,
+                    Err(err) => tracing::warn!(error = %err, "monitor capture fallback failed"),
+                }
+            }
+
+            Ok(out)
+        })
+    }
+
+    fn window_bounds(&self, id: u32) -> Result<WindowBounds> {
+        self.with_window(id, |window| Ok(xcap_bounds(window)))
+    }
+}
+
+fn xcap_bounds(window: &xcap::Window) -> WindowBounds {
+    WindowBounds {
+        x: window.x(),
+        y: window.y(),
+        width: window.width(),
+        height: window.height(),
+    }
+}
+
+/// List the capturable windows through the default backend, sorted by app name and title.
+pub fn list_windows() -> Result<Vec<WindowInfo>> {
+    let mut out = XcapBackend::default().list_windows()?;
+
     // Sort for more stable UX.
     out.sort_by(|a, b| a.app_name.cmp(&b.app_name).then(a.title.cmp(&b.title)));
     Ok(out)
 }
 
-/// Capture the first window whose `app_name` matches `target_app_name`.
-///
-/// If multiple windows share the same `app_name`, the first match is used.
-///
-/// If `max_height` is set, the capture will be downscaled to that height when
-/// larger (preserving aspect ratio).
-pub fn capture_by_app_name(target_app_name: &str, max_height: Option<u32>) -> Result<ie::OwnedImage> {
-    let windows = xcap::Window::all().context("xcap::Window::all")?;
+/// What to capture and how.
+#[derive(Debug, Clone)]
+pub struct CaptureTarget {
+    /// Target window application name (see [`WindowInfo::matches`]).
+    pub app_name: String,
+    /// Match `app_name` against window titles instead of app names.
+    pub match_title: bool,
+    /// Downscale captures taller than this (preserving aspect ratio).
+    pub max_height: Option<u32>,
+    /// Capture the window's monitor instead when the window capture is black.
+    pub monitor_fallback: bool,
+    /// Capture a desktop duplication of the window's output instead when the
+    /// window capture is black (Windows only; works in exclusive fullscreen).
+    pub desktop_duplication: bool,
+    /// Threshold a capture counts as black below (the configured blank threshold).
+    pub blank_threshold: u8,
+    /// Capture exactly this window (see [`WindowInfo::id`]) instead of the
+    /// first one matching `app_name`, e.g. one of several game clients.
+    pub window_id: Option<u32>,
+}
+
+impl CaptureTarget {
+    /// The same target, pinned to a single window.
+    pub fn for_window(&self, id: u32) -> Self {
+        Self {
+            window_id: Some(id),
+            ..self.clone()
+        }
+    }
+}
 
-    let window = windows
+/// Capture the configured target window through the default backend.
+pub fn capture(target: &CaptureTarget) -> Result<ie::OwnedImage> {
+    capture_with(&XcapBackend::for_target(target), target)
+}
+
+/// All windows whose `app_name` matches the target, e.g. several game clients
+/// running side by side, in window id order so they are numbered consistently.
+///
+/// Pass the same backend on to [`capture_with`] to capture them without
+/// enumerating the windows again.
+pub fn matching_windows(backend: &dyn CaptureBackend, target: &CaptureTarget) -> Result<Vec<WindowInfo>> {
+    let mut out = backend
+        .list_windows()?
         .into_iter()
-        .find(|v| v.app_name() == target_app_name)
-        .ok_or_else(|| anyhow!("window not found: app_name={target_app_name}"))?;
+        .filter(|w| w.matches(&target.app_name, target.match_title))
+        .collect::<Vec<_>>();
+    out.sort_by_key(|w| w.id);
+    Ok(out)
+}
 
-    let img = window.capture_image().context("xcap::Window::capture_image")?;
+/// Capture the target's pinned window, or else the first window it matches
+/// (see [`WindowInfo::matches`]).
+///
+/// If multiple windows match, the first one is used.
+///
+/// If the target's `max_height` is set, the capture will be downscaled to
+/// that height when larger (preserving aspect ratio).
+pub fn capture_with(backend: &dyn CaptureBackend, target: &CaptureTarget) -> Result<ie::OwnedImage> {
+    let id = match target.window_id {
+        Some(id) => id,
+        None => {
+            backend
+                .list_windows()?
+                .into_iter()
+                .find(|v| v.matches(&target.app_name, target.match_title))
+                .ok_or_else(|| anyhow!("window not found: app_name={}", target.app_name))?
+                .id
+        }
+    };
 
-    let mut out = ie::OwnedImage::from_rgba(img.width() as usize, img.as_bytes());
+    let mut out = backend.capture(id)?;
+    downscale(&mut out, target.max_height);
+    Ok(out)
+}
 
+/// Downscale `img` to `max_height` when it is taller (preserving aspect ratio).
+fn downscale(img: &mut ie::OwnedImage, max_height: Option<u32>) {
     if let Some(max_h) = max_height {
-        let h = out.as_image().height();
+        let h = img.as_image().height();
         if h > max_h {
-            out.resize_h(max_h);
+            img.resize_h(max_h);
         }
     }
+}
 
-    Ok(out)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Backend serving fixed windows with a solid frame of the window's size.
+    struct MockBackend {
+        windows: Vec<(WindowInfo, WindowBounds)>,
+        listed: Cell<usize>,
+    }
+
+    impl MockBackend {
+        fn new(windows: &[(u32, &str, &str, u32, u32)]) -> Self {
+            Self {
+                windows: windows
+                    .iter()
+                    .map(|&(id, app_name, title, width, height)| {
+                        let info = WindowInfo {
+                            id,
+                            app_name: app_name.to_string(),
+                            title: title.to_string(),
+                        };
+                        (info, WindowBounds { x: 0, y: 0, width, height })
+                    })
+                    .collect(),
+                listed: Cell::new(0),
+            }
+        }
+    }
+
+    impl CaptureBackend for MockBackend {
+        fn list_windows(&self) -> Result<Vec<WindowInfo>> {
+            self.listed.set(self.listed.get() + 1);
+            Ok(self.windows.iter().map(|(info, _)| info.clone()).collect())
+        }
+
+        fn capture(&self, id: u32) -> Result<ie::OwnedImage> {
+            let bounds = self.window_bounds(id)?;
+            let bytes = vec![id as u8; (bounds.width * bounds.height * 4) as usize];
+            Ok(ie::OwnedImage::from_rgba(bounds.width as usize, &bytes))
+        }
+
+        fn window_bounds(&self, id: u32) -> Result<WindowBounds> {
+            self.windows
+                .iter()
+                .find(|(info, _)| info.id == id)
+                .map(|(_, bounds)| *bounds)
+                .ok_or_else(|| anyhow!("window not found: id={id}"))
+        }
+    }
+
+    fn target(app_name: &str) -> CaptureTarget {
+        CaptureTarget {
+            app_name: app_name.to_string(),
+            match_title: false,
+            max_height: None,
+            monitor_fallback: false,
+            desktop_duplication: false,
+            blank_threshold: ie::DEFAULT_BLANK_THRESHOLD,
+            window_id: None,
+        }
+    }
+
+    fn size(img: &ie::OwnedImage) -> (u32, u32) {
+        let img = img.as_image();
+        (img.width(), img.height())
+    }
+
+    #[test]
+    fn captures_first_matching_window() {
+        let backend = MockBackend::new(&[
+            (1, "Discord", "Discord", 8, 8),
+            (2, "Warframe", "Warframe", 16, 9),
+            (3, "Warframe", "Warframe", 32, 18),
+        ]);
+
+        let img = capture_with(&backend, &target("Warframe")).unwrap();
+        assert_eq!(size(&img), (16, 9));
+        assert_eq!(backend.listed.get(), 1);
+    }
+
+    #[test]
+    fn pinned_window_skips_enumeration() {
+        let backend = MockBackend::new(&[(2, "Warframe", "", 16, 9), (3, "Warframe", "", 32, 18)]);
+
+        let img = capture_with(&backend, &target("Warframe").for_window(3)).unwrap();
+        assert_eq!(size(&img), (32, 18));
+        assert_eq!(backend.listed.get(), 0);
+    }
+
+    #[test]
+    fn missing_window_is_an_error() {
+        let backend = MockBackend::new(&[(1, "Discord", "Discord", 8, 8)]);
+        assert!(capture_with(&backend, &target("Warframe")).is_err());
+    }
+
+    #[test]
+    fn downscales_to_max_height() {
+        let backend = MockBackend::new(&[(1, "Warframe", "", 32, 18)]);
+        let target = CaptureTarget {
+            max_height: Some(9),
+            ..target("Warframe")
+        };
+
+        let img = capture_with(&backend, &target).unwrap();
+        assert_eq!(size(&img), (16, 9));
+    }
+
+    #[test]
+    fn matching_windows_by_title_in_id_order() {
+        let backend = MockBackend::new(&[
+            (7, "", "Warframe", 8, 8),
+            (3, "", "Warframe", 8, 8),
+            (5, "", "Firefox", 8, 8),
+        ]);
+
+        let ids = matching_windows(&backend, &target("warframe"))
+            .unwrap()
+            .into_iter()
+            .map(|w| w.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [3, 7]);
+    }
 }
@@ -0,0 +1,194 @@
+//! Desktop duplication (DXGI) capture, for games in exclusive fullscreen.
+//!
+//! Window and GDI monitor captures come back black while a game owns the
+//! output exclusively, but the duplication API still receives its frames.
+
+use anyhow::{anyhow, bail, Context, Result};
+use windows::core::Interface;
+use windows::Win32::Foundation::{HMODULE, HWND, RECT};
+use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_UNKNOWN;
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D, D3D11_CPU_ACCESS_READ,
+    D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAPPED_SUBRESOURCE, D3D11_MAP_READ, D3D11_SDK_VERSION,
+    D3D11_TEXTURE2D_DESC, D3D11_USAGE_STAGING,
+};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+use windows::Win32::Graphics::Dxgi::{
+    CreateDXGIFactory1, IDXGIAdapter1, IDXGIFactory1, IDXGIOutput, IDXGIOutput1, IDXGIResource,
+    DXGI_OUTDUPL_FRAME_INFO,
+};
+use windows::Win32::Graphics::Gdi::{MonitorFromWindow, MONITOR_DEFAULTTONEAREST};
+use windows::Win32::UI::WindowsAndMessaging::GetWindowRect;
+
+use super::WindowBounds;
+
+/// How long to wait for the first duplicated frame.
+const FRAME_TIMEOUT_MS: u32 = 500;
+
+/// Capture the window with the given id (its `HWND`) from a duplication of
+/// the output it is on, cropped to the window.
+///
+/// A new duplication is set up per call: its first frame is the current
+/// desktop image, while a kept one would only deliver frames on change.
+pub fn capture_window(id: u32) -> Result<ie::OwnedImage> {
+    let hwnd = HWND(id as usize as *mut _);
+    let bounds = window_rect(hwnd)?;
+    let (adapter, output) = output_of(hwnd)?;
+    let desktop = unsafe { output.GetDesc() }.context("IDXGIOutput::GetDesc")?.DesktopCoordinates;
+
+    let visible = bounds
+        .intersect(&rect_bounds(&desktop))
+        .ok_or_else(|| anyhow!("window is not on its output: {bounds:?}"))?;
+
+    let mut device: Option<ID3D11Device> = None;
+    let mut context: Option<ID3D11DeviceContext> = None;
+    unsafe {
+        D3D11CreateDevice(
+            &adapter,
+            D3D_DRIVER_TYPE_UNKNOWN,
+            HMODULE::default(),
+            D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+            None,
+            D3D11_SDK_VERSION,
+            Some(&mut device),
+            None,
+            Some(&mut context),
+        )
+    }
+    .context("D3D11CreateDevice")?;
+    let device = device.ok_or_else(|| anyhow!("D3D11CreateDevice returned no device"))?;
+    let context = context.ok_or_else(|| anyhow!("D3D11CreateDevice returned no context"))?;
+
+    let output = output.cast::<IDXGIOutput1>().context("IDXGIOutput1")?;
+    let duplication = unsafe { output.DuplicateOutput(&device) }.context("IDXGIOutput1::DuplicateOutput")?;
+
+    let mut info = DXGI_OUTDUPL_FRAME_INFO::default();
+    let mut resource: Option<IDXGIResource> = None;
+    unsafe { duplication.AcquireNextFrame(FRAME_TIMEOUT_MS, &mut info, &mut resource) }
+        .context("IDXGIOutputDuplication::AcquireNextFrame")?;
+
+    let frame = resource
+        .ok_or_else(|| anyhow!("duplication returned no frame"))
+        .and_then(|resource| resource.cast::<ID3D11Texture2D>().context("duplicated frame texture"))
+        .and_then(|texture| {
+            let x = (visible.x - desktop.left) as u32;
+            let y = (visible.y - desktop.top) as u32;
+            read_texture(&device, &context, &texture, x, y, visible.width, visible.height)
+        });
+
+    // The frame has to be released even when reading it failed.
+    if let Err(err) = unsafe { duplication.ReleaseFrame() } {
+        tracing::debug!(error = %err, "IDXGIOutputDuplication::ReleaseFrame failed");
+    }
+
+    frame
+}
+
+/// The window's bounds in physical desktop pixels, the space of the output's
+/// desktop coordinates (the DWM frame bounds leave out the invisible resize border).
+fn window_rect(hwnd: HWND) -> Result<WindowBounds> {
+    let mut rect = RECT::default();
+    let dwm = unsafe {
+        DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut rect as *mut RECT as *mut _,
+            std::mem::size_of::<RECT>() as u32,
+        )
+    };
+    if dwm.is_err() {
+        unsafe { GetWindowRect(hwnd, &mut rect) }.context("GetWindowRect")?;
+    }
+    Ok(rect_bounds(&rect))
+}
+
+fn rect_bounds(rect: &RECT) -> WindowBounds {
+    WindowBounds {
+        x: rect.left,
+        y: rect.top,
+        width: (rect.right - rect.left).max(0) as u32,
+        height: (rect.bottom - rect.top).max(0) as u32,
+    }
+}
+
+/// The output (and its adapter) showing the monitor the window is on.
+fn output_of(hwnd: HWND) -> Result<(IDXGIAdapter1, IDXGIOutput)> {
+    let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) };
+    let factory = unsafe { CreateDXGIFactory1::<IDXGIFactory1>() }.context("CreateDXGIFactory1")?;
+
+    // Both enumerations end with DXGI_ERROR_NOT_FOUND.
+    for i in 0.. {
+        let Ok(adapter) = (unsafe { factory.EnumAdapters1(i) }) else {
+            break;
+        };
+        for j in 0.. {
+            let Ok(output) = (unsafe { adapter.EnumOutputs(j) }) else {
+                break;
+            };
+            let desc = unsafe { output.GetDesc() }.context("IDXGIOutput::GetDesc")?;
+            if desc.Monitor == monitor {
+                return Ok((adapter, output));
+            }
+        }
+    }
+
+    bail!("no DXGI output shows the window's monitor")
+}
+
+/// Copy a region of a GPU texture to an image through a CPU-readable staging texture.
+fn read_texture(
+    device: &ID3D11Device,
+    context: &ID3D11DeviceContext,
+    texture: &ID3D11Texture2D,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<ie::OwnedImage> {
+    let mut desc = D3D11_TEXTURE2D_DESC::default();
+    unsafe { texture.GetDesc(&mut desc) };
+    // HDR outputs are duplicated as float16; those aren't supported.
+    if desc.Format != DXGI_FORMAT_B8G8R8A8_UNORM {
+        bail!("unsupported duplicated frame format: {:?}", desc.Format);
+    }
+    if x + width > desc.Width || y + height > desc.Height {
+        bail!("window region {x},{y} {width}x{height} exceeds the {}x{} frame", desc.Width, desc.Height);
+    }
+
+    let staging_desc = D3D11_TEXTURE2D_DESC {
+        Usage: D3D11_USAGE_STAGING,
+        BindFlags: 0,
+        CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+        MiscFlags: 0,
+        ..desc
+    };
+    let mut staging: Option<ID3D11Texture2D> = None;
+    unsafe { device.CreateTexture2D(&staging_desc, None, Some(&mut staging)) }
+        .context("ID3D11Device::CreateTexture2D")?;
+    let staging = staging.ok_or_else(|| anyhow!("CreateTexture2D returned no texture"))?;
+
+    let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+    unsafe {
+        context.CopyResource(&staging, texture);
+        context.Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+    }
+    .context("ID3D11DeviceContext::Map")?;
+
+    // BGRA rows, `RowPitch` bytes apart.
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+    for row in y..y + height {
+        let line = unsafe {
+            std::slice::from_raw_parts(
+                (mapped.pData as *const u8).add(row as usize * mapped.RowPitch as usize + x as usize * 4),
+                width as usize * 4,
+            )
+        };
+        for px in line.chunks_exact(4) {
+            rgba.extend_from_slice(&[px[2], px[1], px[0], 255]);
+        }
+    }
+    unsafe { context.Unmap(&staging, 0) };
+
+    Ok(ie::OwnedImage::from_rgba(width as usize, &rgba))
+}
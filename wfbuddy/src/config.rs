@@ -3,7 +3,8 @@
 //! Stored as JSON in a platform-appropriate config directory.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -14,7 +15,8 @@ pub struct Config {
     /// Target window application name (from `xcap::Window::app_name()`).
     ///
     /// This is reasonably stable across restarts. If multiple windows share the
-    /// same app name, the first match is used.
+    /// same app name, the first match is used. Windows without an app name are
+    /// matched by title instead (see [`Config::match_window_title`]).
     pub app_name: String,
 
     /// Poll interval (seconds) for lightweight screen checks.
@@ -25,6 +27,177 @@ pub struct Config {
 
     /// Optional max capture height (downscales large captures for performance).
     pub max_capture_height: Option<u32>,
+
+    /// Language the game client is set to (OCR models, item names, header strings).
+    #[serde(default)]
+    pub client_language: data::Language,
+
+    /// Port of the localhost JSON-lines event server (`None` = disabled).
+    ///
+    /// Read at startup; changing it requires a restart.
+    #[serde(default)]
+    pub event_server_port: Option<u16>,
+
+    /// URL that receives a JSON POST whenever a relic reward is selected.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+
+    /// Fall back to capturing the monitor when the window capture comes back
+    /// black in borderless/windowed mode. Exclusive fullscreen isn't covered.
+    #[serde(default)]
+    pub monitor_capture_fallback: bool,
+
+    /// Fall back to a DXGI desktop duplication of the game's monitor when the
+    /// window capture comes back black, which also works in exclusive
+    /// fullscreen. Windows only; tried before [`Config::monitor_capture_fallback`].
+    #[serde(default)]
+    pub desktop_duplication: bool,
+
+    /// Minimum OCR confidence (0..1) for a recognized line to be kept.
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f32,
+
+    /// Platinum value of one forma blueprint when "Valued Forma" is on
+    /// (`None` = [`data::forma_plat_value`]).
+    #[serde(default)]
+    pub forma_plat_override: Option<f32>,
+
+    /// In-game HUD scale (1.0 = 100%), used to size the reward screen probes.
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+
+    /// Fixed party header region (fractions of the capture) for moved HUD
+    /// elements; `None` searches the default avatar bar.
+    #[serde(default)]
+    pub party_header_roi: Option<ie::RelativeRect>,
+
+    /// Rewards worth less platinum than this are dimmed and tagged in the
+    /// reward list (0 = off). They still count towards the totals.
+    #[serde(default)]
+    pub min_plat: f32,
+
+    /// How long (ms) reward mode stays active after the party header was last
+    /// seen, before the reward screen is considered closed.
+    #[serde(default = "default_reward_grace_ms")]
+    pub reward_grace_ms: u64,
+
+    /// Interval (ms) between reward screen captures while in reward mode.
+    #[serde(default = "default_auto_check_ms")]
+    pub auto_check_ms: u64,
+
+    /// Text size factor of the reward list, independent of the rest of the
+    /// window (see [`REWARD_FONT_SCALE`]).
+    #[serde(default = "default_reward_font_scale")]
+    pub reward_font_scale: f32,
+
+    /// Count a reward shown in several slots once in the reward totals
+    /// instead of once per slot.
+    #[serde(default)]
+    pub count_duplicates_once: bool,
+
+    /// Also enter reward mode when `EE.log` reports the reward screen.
+    ///
+    /// The game flushes its log late, so this only helps when a line arrives
+    /// before the party header poll notices the screen. Read at startup.
+    #[serde(default)]
+    pub ee_log_trigger: bool,
+
+    /// `EE.log` location (`None` = the default under the local app data dir).
+    #[serde(default)]
+    pub ee_log_path: Option<PathBuf>,
+
+    /// Captures whose sampled channels all stay at or below this (and are
+    /// flat) are treated as blank and skipped (see [`ie::OwnedImage::is_blank`]).
+    #[serde(default = "default_blank_threshold")]
+    pub blank_threshold: u8,
+
+    /// Where the selection highlight is probed within a reward slot. Only
+    /// needs changing if a UI update moves the indicator; edit the file to retune.
+    #[serde(default)]
+    pub selection_probe: ie::screen::relicreward::SelectionProbe,
+
+    /// Watch every window matching `app_name` (several game clients) instead
+    /// of only the first; reward captures follow the client the reward screen
+    /// was seen on.
+    #[serde(default)]
+    pub multi_client: bool,
+
+    /// How the relic reward list is drawn.
+    #[serde(default)]
+    pub reward_list_style: RewardListStyle,
+
+    /// Never use the network (item data, prices, webhook); also enabled by
+    /// `WFBUDDY_OFFLINE=1`. Item data and prices come from the copies cached by
+    /// the last online start, without those there is no item data. Read at startup.
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Treat `app_name` as part of the window title, for platforms that report
+    /// the app name of some windows wrongly rather than not at all.
+    #[serde(default)]
+    pub match_window_title: bool,
+
+    /// Read the mission type from the in-mission HUD every few seconds (one
+    /// OCR pass each time), to show it next to the relic rewards.
+    #[serde(default)]
+    pub read_mission_type: bool,
+}
+
+/// Rendering of the relic reward list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardListStyle {
+    /// Full rows with tags, market buttons, session totals and controls.
+    #[default]
+    Detailed,
+    /// One plain line per reward (name and value) and nothing else, for
+    /// capturing the window into a stream layout.
+    Minimal,
+}
+
+impl RewardListStyle {
+    pub const ALL: [RewardListStyle; 2] = [RewardListStyle::Detailed, RewardListStyle::Minimal];
+}
+
+impl std::fmt::Display for RewardListStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RewardListStyle::Detailed => write!(f, "Detailed"),
+            RewardListStyle::Minimal => write!(f, "Minimal"),
+        }
+    }
+}
+
+/// Accepted range of [`Config::reward_grace_ms`].
+pub const REWARD_GRACE_MS: std::ops::RangeInclusive<u64> = 500..=15_000;
+/// Accepted range of [`Config::auto_check_ms`], the app ticks every 250ms so
+/// anything shorter would not poll any faster.
+pub const AUTO_CHECK_MS: std::ops::RangeInclusive<u64> = 250..=5_000;
+
+/// Accepted range of [`Config::reward_font_scale`].
+pub const REWARD_FONT_SCALE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+fn default_reward_font_scale() -> f32 {
+    1.0
+}
+
+fn default_reward_grace_ms() -> u64 {
+    3000
+}
+
+fn default_auto_check_ms() -> u64 {
+    350
+}
+
+fn default_blank_threshold() -> u8 {
+    ie::DEFAULT_BLANK_THRESHOLD
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+fn default_min_confidence() -> f32 {
+    ie::DEFAULT_MIN_CONFIDENCE
 }
 
 impl Default for Config {
@@ -34,11 +207,79 @@ impl Default for Config {
             poll_delay_s: 1.0,
             theme: ie::Theme::WHITE,
             max_capture_height: Some(1080),
+            client_language: data::Language::default(),
+            event_server_port: None,
+            webhook_url: None,
+            monitor_capture_fallback: false,
+            desktop_duplication: false,
+            min_confidence: ie::DEFAULT_MIN_CONFIDENCE,
+            forma_plat_override: None,
+            ui_scale: default_ui_scale(),
+            party_header_roi: None,
+            min_plat: 0.0,
+            reward_grace_ms: default_reward_grace_ms(),
+            auto_check_ms: default_auto_check_ms(),
+            reward_font_scale: default_reward_font_scale(),
+            count_duplicates_once: false,
+            ee_log_trigger: false,
+            ee_log_path: None,
+            blank_threshold: default_blank_threshold(),
+            selection_probe: Default::default(),
+            multi_client: false,
+            reward_list_style: RewardListStyle::default(),
+            offline: false,
+            match_window_title: false,
+            read_mission_type: false,
         }
     }
 }
 
 impl Config {
+    /// Capture settings for the configured target window.
+    pub fn capture_target(&self) -> crate::capture::CaptureTarget {
+        crate::capture::CaptureTarget {
+            app_name: self.app_name.clone(),
+            match_title: self.match_window_title,
+            max_height: self.max_capture_height,
+            monitor_fallback: self.monitor_capture_fallback,
+            desktop_duplication: self.desktop_duplication,
+            blank_threshold: self.blank_threshold,
+            window_id: None,
+        }
+    }
+
+    /// Offline mode from the config or the `WFBUDDY_OFFLINE=1` environment variable.
+    pub fn is_offline(&self) -> bool {
+        self.offline || std::env::var("WFBUDDY_OFFLINE").as_deref() == Ok("1")
+    }
+
+    /// Reward mode grace period, clamped to [`REWARD_GRACE_MS`] (the file may be hand-edited).
+    pub fn reward_grace(&self) -> Duration {
+        Duration::from_millis(self.reward_grace_ms.clamp(*REWARD_GRACE_MS.start(), *REWARD_GRACE_MS.end()))
+    }
+
+    /// Reward capture interval, clamped to [`AUTO_CHECK_MS`].
+    pub fn auto_check_interval(&self) -> Duration {
+        Duration::from_millis(self.auto_check_ms.clamp(*AUTO_CHECK_MS.start(), *AUTO_CHECK_MS.end()))
+    }
+
+    /// Restore only the reward list display settings (minimum platinum, text
+    /// size, duplicate totals, style) to their defaults; theme, window and
+    /// detection settings are kept.
+    pub fn reset_reward_list_defaults(&mut self) {
+        let defaults = Self::default();
+        self.min_plat = defaults.min_plat;
+        self.reward_font_scale = defaults.reward_font_scale;
+        self.count_duplicates_once = defaults.count_duplicates_once;
+        self.reward_list_style = defaults.reward_list_style;
+    }
+
+    /// Reward list text size for a base size in pixels, with the factor clamped
+    /// to [`REWARD_FONT_SCALE`].
+    pub fn reward_font_size(&self, base: f32) -> f32 {
+        base * self.reward_font_scale.clamp(*REWARD_FONT_SCALE.start(), *REWARD_FONT_SCALE.end())
+    }
+
     /// Path to the config file.
     pub fn path() -> Result<PathBuf> {
         let base = dirs::config_dir().context("config_dir() unavailable")?;
@@ -68,13 +309,31 @@ impl Config {
     }
 
     /// Save configuration to disk.
+    ///
+    /// Writes to a temporary file, fsyncs it and renames it over the old config,
+    /// so a crash or power loss mid-save never leaves a truncated config behind.
     pub fn save(&self) -> Result<()> {
         let path = Self::path()?;
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
-        }
         let json = serde_json::to_string_pretty(self).context("serialize config")?;
-        fs::write(&path, json).with_context(|| format!("write {:?}", path))?;
-        Ok(())
+        write_atomic(&path, json.as_bytes())
+    }
+}
+
+/// Write `bytes` to a temporary file next to `path`, fsync it and rename it
+/// over `path`, so readers only ever see the old or the new contents.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+
+    let tmp = path.with_extension("json.tmp");
+    {
+        let mut file = fs::File::create(&tmp).with_context(|| format!("create {:?}", tmp))?;
+        file.write_all(bytes).with_context(|| format!("write {:?}", tmp))?;
+        file.sync_all().with_context(|| format!("sync {:?}", tmp))?;
     }
+    fs::rename(&tmp, path).with_context(|| format!("rename {:?} to {:?}", tmp, path))?;
+    Ok(())
 }
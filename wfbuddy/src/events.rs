@@ -0,0 +1,132 @@
+//! Structured detection events for external tools (OBS overlays, bots, ...).
+//!
+//! Events are serialized as newline-delimited JSON. The only built-in sink is
+//! a localhost TCP server that broadcasts each event to every connected client.
+
+use std::io::Write;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// A detection result worth telling the outside world about.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DetectionEvent<'a> {
+    /// The detected relic rewards changed.
+    Rewards {
+        rewards: &'a ie::screen::relicreward::Rewards,
+    },
+    /// A reward slot was selected.
+    Selected { index: usize, name: &'a str },
+}
+
+/// Receiver for detection events. Implementations must not block for long,
+/// since events are emitted from the UI update loop.
+pub trait EventSink: Send {
+    fn emit(&self, event: &DetectionEvent);
+}
+
+/// JSON-lines server on `127.0.0.1:<port>`.
+///
+/// Dropping it stops and joins the accept thread (see [`JsonLinesServer::shutdown`]).
+pub struct JsonLinesServer {
+    clients: Arc<Mutex<Vec<TcpStream>>>,
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+impl JsonLinesServer {
+    /// Bind the listener and start accepting clients on a background thread.
+    pub fn bind(port: u16) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .with_context(|| format!("bind 127.0.0.1:{port}"))?;
+        let addr = listener.local_addr().context("event server local_addr")?;
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let accept_thread = {
+            let clients = clients.clone();
+            let stop = stop.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Acquire) {
+                        break;
+                    }
+                    match stream {
+                        Ok(stream) => {
+                            // emit runs on the UI thread, a client that stops reading
+                            // must not be able to block it
+                            if let Err(err) = stream.set_nonblocking(true) {
+                                tracing::warn!(error = %err, "failed to make event client non-blocking");
+                                continue;
+                            }
+                            tracing::info!(peer = ?stream.peer_addr().ok(), "event client connected");
+                            clients.lock().unwrap().push(stream);
+                        }
+                        Err(err) => tracing::warn!(error = %err, "event client accept failed"),
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            clients,
+            addr,
+            stop,
+            accept_thread: Some(accept_thread),
+        })
+    }
+
+    /// Stop accepting clients, disconnect the current ones and join the accept thread.
+    ///
+    /// The thread blocks in `accept`, so it is woken with a throwaway local
+    /// connection after the stop flag is set. Idempotent.
+    pub fn shutdown(&mut self) {
+        let Some(thread) = self.accept_thread.take() else {
+            return;
+        };
+
+        self.stop.store(true, Ordering::Release);
+        if let Err(err) = TcpStream::connect(self.addr) {
+            // Without the wake-up the thread would block until the next client;
+            // leave it detached rather than hang the caller.
+            tracing::warn!(error = %err, "failed to wake the event server; not joining it");
+            return;
+        }
+        if thread.join().is_err() {
+            tracing::warn!("event server thread panicked");
+        }
+        self.clients.lock().unwrap().clear();
+    }
+}
+
+impl Drop for JsonLinesServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl EventSink for JsonLinesServer {
+    fn emit(&self, event: &DetectionEvent) {
+        let mut line = match serde_json::to_vec(event) {
+            Ok(v) => v,
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to serialize event");
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        // Drop clients whose connection went away, or that fell behind far enough
+        // to fill their socket buffer (`WouldBlock`) rather than wait on them.
+        self.clients
+            .lock()
+            .unwrap()
+            .retain_mut(|client| client.write_all(&line).is_ok());
+    }
+}
@@ -1,157 +1,81 @@
-use std::{fs::File, io::{Read, Seek}, path::PathBuf, sync::{mpsc::{Receiver, Sender}, Arc, RwLock}, time::{Duration, Instant}};
-use notify::Watcher;
-use regex::Regex;
-use crate::util::OwnedCaptures;
+//! Optional `EE.log` trigger.
+//!
+//! The game writes its log in batches (often 10+ seconds late), so the log is
+//! only used to enter reward mode early when a line does arrive in time; the
+//! party header poll keeps running as the reliable path.
 
-const MIN_DELAY_MS: u64 = 100;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 
-pub type EventReceiver = Receiver<(Instant, OwnedCaptures)>;
-type Watching = Arc<RwLock<Vec<(Regex, Sender<(Instant, OwnedCaptures)>)>>>;
+use anyhow::{Context, Result};
 
-#[derive(Debug)]
-pub enum LogWatcherStatus<'a> {
-	Watching,
-	Stopped,
-	Failed(&'a anyhow::Error),
+/// Log lines that mean the relic reward screen is (about to be) shown.
+const REWARD_TRIGGERS: [&str; 2] = ["Pause countdown done", "Got rewards"];
+
+/// Default `EE.log` location (`%LOCALAPPDATA%\Warframe\EE.log`).
+pub fn default_log_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("Warframe").join("EE.log"))
 }
 
-enum LogWatcherThread<R> {
-	Handle(std::thread::JoinHandle<R>),
-	Joined(R),
-	Error(anyhow::Error),
+/// Whether a log line signals the relic reward screen.
+pub fn is_reward_trigger(line: &str) -> bool {
+    REWARD_TRIGGERS.iter().any(|trigger| line.contains(trigger))
 }
 
-pub struct LogWatcher {
-	watching: Watching,
-	thread: LogWatcherThread<Result<(), anyhow::Error>>,
+/// Reads lines appended to a log file since the last call.
+///
+/// Polled from the UI tick rather than a watcher thread; a read only touches
+/// the bytes written since the previous one.
+pub struct LogTail {
+    path: PathBuf,
+    position: u64,
+    // The game can flush in the middle of a line; keep the rest for the next read.
+    partial_line: String,
 }
 
-impl LogWatcher {
-	pub fn watch(log_file: impl Into<PathBuf>) -> Result<Self, anyhow::Error> {
-		let line_regex = Regex::new(r"^(?<time>[\d\.]+) (?<rest>.+)$").unwrap();
-		
-		let log_file = log_file.into();
-		let watching = Watching::default();
-		let thread = {
-			let watching = watching.clone();
-			std::thread::spawn(move || -> Result<(), anyhow::Error> {
-				let (tx, rs) = std::sync::mpsc::channel();
-				let mut watcher = notify::RecommendedWatcher::new(tx, Default::default())?;
-				watcher.watch(&log_file, notify::RecursiveMode::NonRecursive)?;
-				
-				let mut position = File::open(&log_file)?.seek(std::io::SeekFrom::End(0))?;
-				// warframe can log in batches, splitting up lines, this way we will always have whole lines
-				let mut partial_line = String::new();
-				let mut start = Instant::now();
-				
-				while let Ok(Ok(event)) = rs.recv() {
-					if event.kind != notify::EventKind::Modify(notify::event::ModifyKind::Data(notify::event::DataChange::Any)) {continue};
-					// println!("received change {event:?}");
-					
-					let mut f = File::open(&log_file)?;
-					let len = f.seek(std::io::SeekFrom::End(0))?;
-					if len == 0 {continue}
-					
-					if len < position {
-						println!("reset log position {len} {position}");
-						position = 0;
-					}
-					
-					f.seek(std::io::SeekFrom::Start(position))?;
-					let mut buf = Vec::new();
-					let read_count = f.read_to_end(&mut buf)?;
-					position += read_count as u64;
-					let Ok(s) = str::from_utf8(&buf[..read_count]) else {
-						println!("Failed converting read data into string");
-						continue;
-					};
-					
-					let watching = watching.read().unwrap();
-					
-					partial_line.push_str(&s);
-					let mut index = 0;
-					// fucking hate you return carriage, what year is it, 19th century?
-					while let Some(offset) = partial_line[index..].find("\r\n") {
-						'c: {
-							let line = &partial_line[index..index + offset];
-							// println!("log line: {line}");
-							let Some(cap) = line_regex.captures(line) else {break 'c};
-							let Ok(offset_time) = cap["time"].parse::<f32>() else {break 'c};
-							let rest = &cap["rest"];
-							// println!("{offset_time} {rest}");
-							let offset_duration = Duration::from_secs_f32(offset_time);
-							let estimated_start = Instant::now() - offset_duration;
-							if estimated_start < start {
-								start = estimated_start;
-								println!("new estimated start {start:?}");
-							}
-							
-							for (regex, wtx) in watching.iter() {
-								// println!("testing\n\t`{rest}`\nagainst\n\t{regex:?}");
-								let Some(cap2) = regex.captures(rest) else {continue};
-								// println!("{regex:?} {offset_time} {rest}");
-								_ = wtx.send((start + offset_duration, (regex, cap2).into()));
-							}
-						}
-						
-						index += offset + 2;
-					}
-					
-					if index >= partial_line.len() {
-						partial_line.clear();
-					} else {
-						partial_line = partial_line[index..].to_string();
-					}
-					
-					std::thread::sleep(Duration::from_millis(MIN_DELAY_MS));
-				}
-				
-				println!("ending watch");
-				
-				Ok(())
-			})
-		};
-		
-		Ok(Self {
-			watching,
-			thread: LogWatcherThread::Handle(thread),
-		})
-	}
-	
-	pub fn watch_event(&self, regex: Regex, tx: Sender<(Instant, OwnedCaptures)>) {
-		self.watching
-			.write()
-			.unwrap()
-			.push((regex, tx));
-	}
-	
-	pub fn status<'a>(&'a mut self) -> LogWatcherStatus<'a> {
-		if let LogWatcherThread::Handle(handle) = &self.thread {
-			if !handle.is_finished() {
-				return LogWatcherStatus::Watching;
-			}
-			
-			// We do unsafe code since std::mem::take, swap, and replace need existing values or a default, which we dont have yet
-			unsafe {
-				let thread_ptr = &mut self.thread as *mut _;
-				let thread = std::ptr::read(thread_ptr);
-				let LogWatcherThread::Handle(handle) = thread else {unreachable!()};
-				match handle.join() {
-					Ok(v) => thread_ptr.write(LogWatcherThread::Joined(v)),
-					Err(e) => thread_ptr.write(LogWatcherThread::Error(anyhow::Error::msg(format!("Thread panic {e:#?}")))),
-				}
-			}
-		}
-		
-		match &self.thread {
-			LogWatcherThread::Handle(_) => unreachable!(),
-			
-			LogWatcherThread::Joined(r) => match r {
-				Ok(_) => LogWatcherStatus::Stopped,
-				Err(e) => LogWatcherStatus::Failed(e),
-			}
-			
-			LogWatcherThread::Error(e) => LogWatcherStatus::Failed(e)
-		}
-	}
-}
\ No newline at end of file
+impl LogTail {
+    /// Start tailing at the current end of the file, skipping what is already there.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let position = File::open(&path)
+            .with_context(|| format!("open {:?}", path))?
+            .seek(SeekFrom::End(0))
+            .with_context(|| format!("seek {:?}", path))?;
+
+        Ok(Self {
+            path,
+            position,
+            partial_line: String::new(),
+        })
+    }
+
+    /// Complete lines appended since the last call.
+    pub fn poll(&mut self) -> Result<Vec<String>> {
+        let mut file = File::open(&self.path).with_context(|| format!("open {:?}", self.path))?;
+        let len = file.seek(SeekFrom::End(0))?;
+        if len < self.position {
+            // The game truncates the log on start.
+            self.position = 0;
+            self.partial_line.clear();
+        }
+        if len == self.position {
+            return Ok(Vec::new());
+        }
+
+        file.seek(SeekFrom::Start(self.position))?;
+        let mut buf = Vec::new();
+        self.position += file.read_to_end(&mut buf)? as u64;
+        self.partial_line.push_str(&String::from_utf8_lossy(&buf));
+
+        let Some(end) = self.partial_line.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+        let lines = self.partial_line[..end]
+            .lines()
+            .map(|line| line.trim_end_matches('\r').to_string())
+            .collect();
+        self.partial_line.drain(..=end);
+        Ok(lines)
+    }
+}
@@ -5,6 +5,10 @@
 mod app;
 mod capture;
 mod config;
+mod events;
+mod logwatcher;
+mod stats;
+mod webhook;
 
 fn main() -> iced::Result {
     // Structured logging. Use `RUST_LOG=info` etc.
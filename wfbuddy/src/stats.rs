@@ -0,0 +1,70 @@
+//! Lifetime reward statistics.
+//!
+//! Stored as JSON next to the config and updated whenever a relic reward is
+//! selected, so the totals survive restarts.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// All-time totals of the selected relic rewards.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stats {
+    /// Platinum value of all selected rewards.
+    #[serde(default)]
+    pub total_plat: f64,
+
+    /// Ducat value of all selected rewards.
+    #[serde(default)]
+    pub total_ducats: u64,
+
+    /// Number of reward selections, i.e. relics opened.
+    #[serde(default)]
+    pub relics_opened: u64,
+}
+
+impl Stats {
+    /// Path to the stats file.
+    pub fn path() -> Result<PathBuf> {
+        let base = dirs::config_dir().context("config_dir() unavailable")?;
+        Ok(base.join("wfbuddy_stats.json"))
+    }
+
+    /// Load the stats from disk, starting from zero on a missing or broken file.
+    pub fn load_or_default() -> Self {
+        match Self::try_load() {
+            Ok(stats) => stats,
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to load stats; starting from zero");
+                Self::default()
+            }
+        }
+    }
+
+    /// Try to load the stats from disk.
+    pub fn try_load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
+        let stats = serde_json::from_str(&json).with_context(|| format!("parse {:?}", path))?;
+        Ok(stats)
+    }
+
+    /// Save the stats to disk (atomically, like the config).
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let json = serde_json::to_string_pretty(self).context("serialize stats")?;
+        crate::config::write_atomic(&path, json.as_bytes())
+    }
+
+    /// Add one selected reward.
+    pub fn record(&mut self, platinum: f32, ducats: u32) {
+        self.total_plat += platinum as f64;
+        self.total_ducats += ducats as u64;
+        self.relics_opened += 1;
+    }
+}
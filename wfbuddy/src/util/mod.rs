@@ -1,2 +0,0 @@
-mod owned_captures;
-pub use owned_captures::*;
\ No newline at end of file
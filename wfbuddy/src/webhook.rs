@@ -0,0 +1,25 @@
+//! Best-effort webhook notifications.
+
+use serde::Serialize;
+
+/// Body POSTed when a relic reward is selected.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectionPayload {
+    pub name: String,
+    pub platinum: f32,
+    pub ducats: u32,
+    pub session_platinum: f32,
+    pub session_ducats: u32,
+}
+
+/// POST `payload` as JSON to `url` on a background thread.
+///
+/// Never blocks the caller; failures are only logged so a slow or broken
+/// webhook can't stall polling.
+pub fn post_selection(url: String, payload: SelectionPayload) {
+    std::thread::spawn(move || {
+        if let Err(err) = ureq::post(&url).send_json(&payload) {
+            tracing::warn!(error = %err, url = %url, "webhook request failed");
+        }
+    });
+}